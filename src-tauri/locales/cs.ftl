@@ -0,0 +1,190 @@
+## hlavní obrazovka
+
+settings = nastavení
+feedback = hlášení chyb a nápady na vylepšení
+manual = návod k použití
+pick-files = výběr souborů
+add-files = přidat další
+remove = odstranit
+send = odeslat
+save = uložit
+save-and-close = uložit a zavřít
+close-without-saving = zavřít bez uložení
+yes = ano
+no = ne
+other-recipients = ostatní...
+last-recipients = poslední příjemci
+author-select = kdo odesílá?
+report-new = nový protokol
+report-attach = přiložit
+file-already-selected = soubor { $name } už je vybrán
+file-unreadable = soubor { $name } nelze přečíst
+thumbnails-unavailable = náhledy PDF nejsou dostupné, chybí knihovna pdfium
+merge-pdfs-on = PDF budou sloučena do jednoho
+merge-pdfs-off = sloučit PDF do jednoho
+vehicle-vin = VIN
+vehicle-plate = SPZ
+vehicle-job-number = zakázka
+vehicle-not-found = nenalezeno
+
+## odeslání
+
+send-confirm-title = opravdu odeslat?
+send-confirm-author = odesílá: { $name }
+send-confirm-profile = účet: { $label } ({ $mail })
+receipt-delivery-status = doručenka
+receipt-read = potvrzení přečtení
+validation-title = E-mail nelze odeslat
+validation-no-file = není vybrán žádný soubor
+validation-no-recipients = není vybrán žádný příjemce
+send-error-title = E-mail se nepodařilo odeslat
+send-error-retry = soubory i příjemci zůstali vybráni, odeslání můžete zopakovat
+sent-copy-title = E-mail byl odeslán, ale kopie nebyla uložena
+sent-copy-retry = uložení se bude automaticky opakovat
+imap-no-connection = IMAP server není dostupný
+imap-rejected = IMAP server kopii odmítl: { $reason }
+imap-invalid-response = IMAP server odpověděl neplatně
+mailbox-failed-title = E-mail nebyl doručen
+
+## ostatní příjemci
+
+other-title = zadejte prosím E-mailové adresy
+other-add = přidat další E-mail
+other-placeholder = Zadejte prosím E-mail
+other-promote = do seznamu
+address-missing-parts = chybí část před nebo za @
+address-unbalanced = neuzavřená závorka < >
+address-invalid-user = neplatná část před @
+address-invalid-domain = neplatná doména za @
+address-invalid-characters = nepovolené znaky
+promote-title = uložit adresu do seznamu
+promote-mail = E-mail:
+promote-name = jméno:
+promote-category = kategorie:
+promote-mechanics = mechanici
+promote-technics = technici
+promote-wrong-password = nesprávné heslo
+promote-missing-name = zadejte prosím jméno
+promote-category-full = v kategorii není volné místo
+promote-save-failed = seznam se nepodařilo uložit
+
+## zpětná vazba
+
+feedback-placeholder = Zadejte prosím zprávu pro vývojáře
+feedback-sent = Zpětná vazba byla odeslána, děkujeme!
+feedback-failed = Nepodařilo se odeslat zpětnou vazbu.
+feedback-contact-admin = Kontaktujte prosím administrátora!
+
+## návody
+
+manual-title = Návod k použití
+settings-manual-title = Návod k použití nastavení
+
+## průvodce
+
+tutorial-start = spustit průvodce
+tutorial-skip = přeskočit průvodce
+tutorial-progress = krok { $step } z { $count }
+tutorial-recipients = Vyberte příjemce kliknutím na jeho jméno
+tutorial-other-button = Příjemce mimo seznam přidáte tlačítkem "ostatní..."
+tutorial-other-dialog = Přidejte E-mail tlačítkem "přidat další E-mail" a okno zavřete křížkem
+tutorial-file-picker = Vyberte soubory k odeslání
+tutorial-send = Klikněte na náklaďák a E-mail odešlete
+tutorial-select-person = Vyberte osobu, kterou chcete přidat nebo upravit
+tutorial-edit-person = Vyplňte jméno a E-mail vybrané osoby
+tutorial-save-settings = Změny uložte tlačítkem "uložit a zavřít"
+
+## nastavení
+
+settings-password-title = Zadejte prosím heslo pro vstup do nastavení
+settings-password-placeholder = Heslo
+settings-password-check = ověřit
+settings-config = config
+saved-addresses = uložené adresy
+settings-select-person = Vyberte prosím osobu pro úpravu údajů
+discard-title = Opravdu si přejete odejít bez uložení?
+discard-back = návrat zpět do nastavení
+discard-leave = odejít bez uložení
+invalid-mail-title = Následující osoby mají neplatný E-mail
+invalid-mail-hint = Upravte nebo smažte je
+person-name = jméno
+person-mail = e-mail
+person-certificate = certifikát
+person-certificate-placeholder = cesta k certifikátu S/MIME
+addresses-title = uložené adresy pro našeptávání
+addresses-block = blokovat
+addresses-unblock = odblokovat
+addresses-delete = smazat
+
+## konfigurace
+
+config-title = úprava konfiguračního souboru
+config-locale = jazyk:
+config-sender-name = Jméno odesilatele:
+config-sender-mail = E-mail odesilatele:
+config-sender-password = heslo odesilatele:
+config-sender-auth = přihlášení odesilatele:
+config-auth-password = heslo
+config-auth-oauth2 = oauth2
+config-oauth-client-id = oauth2 client id:
+config-oauth-client-secret = oauth2 client secret:
+config-oauth-refresh-token = oauth2 refresh token:
+config-oauth-token-endpoint = oauth2 token endpoint:
+config-title-template = předmět E-mailu:
+config-body = text E-mailu:
+config-smtp-transport = smtp transport:
+config-feedback-mail = feedback E-mail:
+config-feedback-recepient = feedback příjemce:
+config-feedback-subject = feedback předmět:
+config-settings-password = heslo nastavení:
+config-watch-directory = sledovaná složka:
+config-watch-pattern = maska souborů:
+config-watch-max-age = stáří souborů (hodin, 0 = bez limitu):
+config-watch-archive = archivovat odeslané:
+config-post-send-action = po odeslání souborů:
+config-post-send-leave = ponechat
+config-post-send-move = přesunout do archivu
+config-post-send-rename = přejmenovat
+config-archive-directory = archivní složka:
+config-plate-pattern = vzor SPZ:
+config-job-number-pattern = vzor čísla zakázky:
+config-merge-file-name = název sloučeného PDF:
+config-attachment-name = název přílohy:
+config-keep-recipients = ponechat příjemce po odeslání:
+config-author-in-sender-name = jméno mechanika ve jménu odesílatele:
+config-profile-selector = výběr účtu na hlavní obrazovce:
+config-delivery-status = žádat o doručenku (DSN):
+config-read-receipt = žádat o potvrzení přečtení:
+config-dkim-selector = DKIM selektor:
+config-dkim-domain = DKIM doména:
+config-dkim-private-key = DKIM soukromý klíč (cesta):
+config-smime-certificate = S/MIME certifikát PKCS#12 (cesta):
+config-smime-password = S/MIME heslo certifikátu:
+config-smime-encrypt = S/MIME šifrování pro příjemce:
+config-imap-host = IMAP server pro doručenky:
+config-imap-port = IMAP port:
+config-imap-username = IMAP uživatel:
+config-imap-password = IMAP heslo:
+config-imap-folder = IMAP složka s doručenkami:
+config-imap-sent-folder = IMAP složka pro kopie odeslaných (prázdné = neukládat):
+config-imap-tls = IMAP šifrované spojení (TLS):
+config-default-profile = výchozí účet odesílatele:
+config-profile = další účet odesílatele
+config-profile-remove = odstranit účet
+config-profile-label = název účtu:
+config-profile-add = přidat účet odesílatele
+config-profile-new-label = účet { $number }
+
+## chyby konfigurace a certifikátů
+
+dkim-missing-selector = DKIM: chybí selektor
+dkim-missing-domain = DKIM: chybí doména
+dkim-invalid-key = DKIM: soukromý klíč není platný RSA klíč ve formátu PEM
+dkim-unreadable-key = DKIM: soubor se soukromým klíčem nelze přečíst
+smime-encrypt-needs-certificate = S/MIME: šifrování vyžaduje certifikát odesílatele
+smime-invalid-certificate = S/MIME: certifikát nelze otevřít, zkontrolujte soubor PKCS#12 a heslo
+smime-unreadable-certificate = S/MIME: soubor s certifikátem nelze přečíst
+recipient-missing-certificate = příjemce nemá uložený certifikát
+recipient-unreadable-certificate = certifikát příjemce nelze přečíst
+recipient-other-without-certificate = jednorázové adrese nelze přiřadit certifikát, přidejte příjemce v nastavení
+archive-directory-missing = Přesun po odeslání: není zadána složka archivu
@@ -0,0 +1,190 @@
+## hlavní obrazovka
+
+settings = Einstellungen
+feedback = Fehler melden und Verbesserungsvorschläge
+manual = Bedienungsanleitung
+pick-files = Dateien auswählen
+add-files = weitere hinzufügen
+remove = entfernen
+send = senden
+save = speichern
+save-and-close = speichern und schließen
+close-without-saving = ohne Speichern schließen
+yes = ja
+no = nein
+other-recipients = andere...
+last-recipients = letzte Empfänger
+author-select = wer sendet?
+report-new = neues Protokoll
+report-attach = anhängen
+file-already-selected = Datei { $name } ist bereits ausgewählt
+file-unreadable = Datei { $name } kann nicht gelesen werden
+thumbnails-unavailable = PDF-Vorschauen sind nicht verfügbar, die Bibliothek pdfium fehlt
+merge-pdfs-on = PDFs werden zu einer Datei zusammengeführt
+merge-pdfs-off = PDFs zu einer Datei zusammenführen
+vehicle-vin = FIN
+vehicle-plate = Kennzeichen
+vehicle-job-number = Auftrag
+vehicle-not-found = nicht gefunden
+
+## odeslání
+
+send-confirm-title = wirklich senden?
+send-confirm-author = sendet: { $name }
+send-confirm-profile = Konto: { $label } ({ $mail })
+receipt-delivery-status = Zustellbestätigung
+receipt-read = Lesebestätigung
+validation-title = E-Mail kann nicht gesendet werden
+validation-no-file = keine Datei ausgewählt
+validation-no-recipients = kein Empfänger ausgewählt
+send-error-title = E-Mail konnte nicht gesendet werden
+send-error-retry = Dateien und Empfänger bleiben ausgewählt, das Senden kann wiederholt werden
+sent-copy-title = E-Mail wurde gesendet, aber die Kopie wurde nicht gespeichert
+sent-copy-retry = das Speichern wird automatisch wiederholt
+imap-no-connection = IMAP-Server ist nicht erreichbar
+imap-rejected = IMAP-Server hat die Kopie abgelehnt: { $reason }
+imap-invalid-response = IMAP-Server hat ungültig geantwortet
+mailbox-failed-title = E-Mail wurde nicht zugestellt
+
+## ostatní příjemci
+
+other-title = bitte E-Mail-Adressen eingeben
+other-add = weitere E-Mail hinzufügen
+other-placeholder = Bitte E-Mail eingeben
+other-promote = in die Liste
+address-missing-parts = Teil vor oder nach @ fehlt
+address-unbalanced = nicht geschlossene Klammer < >
+address-invalid-user = ungültiger Teil vor @
+address-invalid-domain = ungültige Domain nach @
+address-invalid-characters = unzulässige Zeichen
+promote-title = Adresse in die Liste speichern
+promote-mail = E-Mail:
+promote-name = Name:
+promote-category = Kategorie:
+promote-mechanics = Mechaniker
+promote-technics = Techniker
+promote-wrong-password = falsches Passwort
+promote-missing-name = bitte Namen eingeben
+promote-category-full = in der Kategorie ist kein Platz frei
+promote-save-failed = die Liste konnte nicht gespeichert werden
+
+## zpětná vazba
+
+feedback-placeholder = Bitte Nachricht für die Entwickler eingeben
+feedback-sent = Rückmeldung wurde gesendet, vielen Dank!
+feedback-failed = Rückmeldung konnte nicht gesendet werden.
+feedback-contact-admin = Bitte wenden Sie sich an den Administrator!
+
+## návody
+
+manual-title = Bedienungsanleitung
+settings-manual-title = Anleitung zu den Einstellungen
+
+## Assistent
+
+tutorial-start = Assistent starten
+tutorial-skip = Assistent überspringen
+tutorial-progress = Schritt { $step } von { $count }
+tutorial-recipients = Wählen Sie einen Empfänger, indem Sie auf seinen Namen klicken
+tutorial-other-button = Empfänger außerhalb der Liste fügen Sie mit der Schaltfläche "andere..." hinzu
+tutorial-other-dialog = Fügen Sie mit "weitere E-Mail hinzufügen" eine E-Mail hinzu und schließen Sie das Fenster mit dem Kreuz
+tutorial-file-picker = Wählen Sie die zu sendenden Dateien
+tutorial-send = Klicken Sie auf den Lkw, um die E-Mail zu senden
+tutorial-select-person = Wählen Sie die Person, die Sie hinzufügen oder bearbeiten möchten
+tutorial-edit-person = Füllen Sie Name und E-Mail der gewählten Person aus
+tutorial-save-settings = Speichern Sie die Änderungen mit "speichern und schließen"
+
+## nastavení
+
+settings-password-title = Bitte Passwort für die Einstellungen eingeben
+settings-password-placeholder = Passwort
+settings-password-check = prüfen
+settings-config = Konfiguration
+saved-addresses = gespeicherte Adressen
+settings-select-person = Bitte Person zum Bearbeiten auswählen
+discard-title = Wirklich ohne Speichern verlassen?
+discard-back = zurück zu den Einstellungen
+discard-leave = ohne Speichern verlassen
+invalid-mail-title = Folgende Personen haben eine ungültige E-Mail
+invalid-mail-hint = Bitte korrigieren oder löschen
+person-name = Name
+person-mail = E-Mail
+person-certificate = Zertifikat
+person-certificate-placeholder = Pfad zum S/MIME-Zertifikat
+addresses-title = gespeicherte Adressen für Vorschläge
+addresses-block = sperren
+addresses-unblock = entsperren
+addresses-delete = löschen
+
+## konfigurace
+
+config-title = Konfigurationsdatei bearbeiten
+config-locale = Sprache:
+config-sender-name = Name des Absenders:
+config-sender-mail = E-Mail des Absenders:
+config-sender-password = Passwort des Absenders:
+config-sender-auth = Anmeldung des Absenders:
+config-auth-password = Passwort
+config-auth-oauth2 = oauth2
+config-oauth-client-id = oauth2 Client-ID:
+config-oauth-client-secret = oauth2 Client-Secret:
+config-oauth-refresh-token = oauth2 Refresh-Token:
+config-oauth-token-endpoint = oauth2 Token-Endpunkt:
+config-title-template = Betreff der E-Mail:
+config-body = Text der E-Mail:
+config-smtp-transport = SMTP-Server:
+config-feedback-mail = Feedback E-Mail:
+config-feedback-recepient = Feedback Empfänger:
+config-feedback-subject = Feedback Betreff:
+config-settings-password = Passwort der Einstellungen:
+config-watch-directory = überwachter Ordner:
+config-watch-pattern = Dateimaske:
+config-watch-max-age = Alter der Dateien (Stunden, 0 = ohne Limit):
+config-watch-archive = gesendete archivieren:
+config-post-send-action = nach dem Senden der Dateien:
+config-post-send-leave = belassen
+config-post-send-move = ins Archiv verschieben
+config-post-send-rename = umbenennen
+config-archive-directory = Archivordner:
+config-plate-pattern = Muster Kennzeichen:
+config-job-number-pattern = Muster Auftragsnummer:
+config-merge-file-name = Name der zusammengeführten PDF:
+config-attachment-name = Name des Anhangs:
+config-keep-recipients = Empfänger nach dem Senden behalten:
+config-author-in-sender-name = Name des Mechanikers im Absendernamen:
+config-profile-selector = Kontoauswahl auf dem Hauptbildschirm:
+config-delivery-status = Zustellbestätigung anfordern (DSN):
+config-read-receipt = Lesebestätigung anfordern:
+config-dkim-selector = DKIM Selektor:
+config-dkim-domain = DKIM Domain:
+config-dkim-private-key = DKIM privater Schlüssel (Pfad):
+config-smime-certificate = S/MIME Zertifikat PKCS#12 (Pfad):
+config-smime-password = S/MIME Passwort des Zertifikats:
+config-smime-encrypt = S/MIME Verschlüsselung für Empfänger:
+config-imap-host = IMAP-Server für Bestätigungen:
+config-imap-port = IMAP Port:
+config-imap-username = IMAP Benutzer:
+config-imap-password = IMAP Passwort:
+config-imap-folder = IMAP Ordner mit Bestätigungen:
+config-imap-sent-folder = IMAP Ordner für Kopien gesendeter E-Mails (leer = nicht speichern):
+config-imap-tls = IMAP verschlüsselte Verbindung (TLS):
+config-default-profile = Standardkonto des Absenders:
+config-profile = weiteres Absenderkonto
+config-profile-remove = Konto entfernen
+config-profile-label = Kontoname:
+config-profile-add = Absenderkonto hinzufügen
+config-profile-new-label = Konto { $number }
+
+## chyby konfigurace a certifikátů
+
+dkim-missing-selector = DKIM: Selektor fehlt
+dkim-missing-domain = DKIM: Domain fehlt
+dkim-invalid-key = DKIM: privater Schlüssel ist kein gültiger RSA-Schlüssel im PEM-Format
+dkim-unreadable-key = DKIM: Datei mit privatem Schlüssel kann nicht gelesen werden
+smime-encrypt-needs-certificate = S/MIME: Verschlüsselung erfordert ein Zertifikat des Absenders
+smime-invalid-certificate = S/MIME: Zertifikat kann nicht geöffnet werden, PKCS#12-Datei und Passwort prüfen
+smime-unreadable-certificate = S/MIME: Zertifikatsdatei kann nicht gelesen werden
+recipient-missing-certificate = Empfänger hat kein gespeichertes Zertifikat
+recipient-unreadable-certificate = Zertifikat des Empfängers kann nicht gelesen werden
+recipient-other-without-certificate = einer einmaligen Adresse kann kein Zertifikat zugeordnet werden, fügen Sie den Empfänger in den Einstellungen hinzu
+archive-directory-missing = Verschieben nach dem Senden: Archivordner ist nicht angegeben
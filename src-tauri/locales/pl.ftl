@@ -0,0 +1,190 @@
+## hlavní obrazovka
+
+settings = ustawienia
+feedback = zgłaszanie błędów i pomysły na ulepszenia
+manual = instrukcja obsługi
+pick-files = wybór plików
+add-files = dodaj kolejne
+remove = usuń
+send = wyślij
+save = zapisz
+save-and-close = zapisz i zamknij
+close-without-saving = zamknij bez zapisywania
+yes = tak
+no = nie
+other-recipients = inni...
+last-recipients = ostatni odbiorcy
+author-select = kto wysyła?
+report-new = nowy protokół
+report-attach = załącz
+file-already-selected = plik { $name } jest już wybrany
+file-unreadable = nie można odczytać pliku { $name }
+thumbnails-unavailable = podglądy PDF są niedostępne, brak biblioteki pdfium
+merge-pdfs-on = pliki PDF zostaną połączone w jeden
+merge-pdfs-off = połącz pliki PDF w jeden
+vehicle-vin = VIN
+vehicle-plate = nr rejestracyjny
+vehicle-job-number = zlecenie
+vehicle-not-found = nie znaleziono
+
+## odeslání
+
+send-confirm-title = na pewno wysłać?
+send-confirm-author = wysyła: { $name }
+send-confirm-profile = konto: { $label } ({ $mail })
+receipt-delivery-status = potwierdzenie doręczenia
+receipt-read = potwierdzenie przeczytania
+validation-title = Nie można wysłać e-maila
+validation-no-file = nie wybrano żadnego pliku
+validation-no-recipients = nie wybrano żadnego odbiorcy
+send-error-title = Nie udało się wysłać e-maila
+send-error-retry = pliki i odbiorcy pozostają wybrani, wysyłkę można powtórzyć
+sent-copy-title = E-mail został wysłany, ale kopia nie została zapisana
+sent-copy-retry = zapis zostanie automatycznie ponowiony
+imap-no-connection = serwer IMAP jest niedostępny
+imap-rejected = serwer IMAP odrzucił kopię: { $reason }
+imap-invalid-response = serwer IMAP odpowiedział nieprawidłowo
+mailbox-failed-title = E-mail nie został doręczony
+
+## ostatní příjemci
+
+other-title = proszę podać adresy e-mail
+other-add = dodaj kolejny e-mail
+other-placeholder = Proszę podać e-mail
+other-promote = do listy
+address-missing-parts = brak części przed lub za @
+address-unbalanced = niezamknięty nawias < >
+address-invalid-user = nieprawidłowa część przed @
+address-invalid-domain = nieprawidłowa domena za @
+address-invalid-characters = niedozwolone znaki
+promote-title = zapisz adres na liście
+promote-mail = E-mail:
+promote-name = imię i nazwisko:
+promote-category = kategoria:
+promote-mechanics = mechanicy
+promote-technics = technicy
+promote-wrong-password = nieprawidłowe hasło
+promote-missing-name = proszę podać imię i nazwisko
+promote-category-full = w kategorii nie ma wolnego miejsca
+promote-save-failed = nie udało się zapisać listy
+
+## zpětná vazba
+
+feedback-placeholder = Proszę wpisać wiadomość dla programistów
+feedback-sent = Opinia została wysłana, dziękujemy!
+feedback-failed = Nie udało się wysłać opinii.
+feedback-contact-admin = Proszę skontaktować się z administratorem!
+
+## návody
+
+manual-title = Instrukcja obsługi
+settings-manual-title = Instrukcja obsługi ustawień
+
+## przewodnik
+
+tutorial-start = uruchom przewodnik
+tutorial-skip = pomiń przewodnik
+tutorial-progress = krok { $step } z { $count }
+tutorial-recipients = Wybierz odbiorcę, klikając jego nazwisko
+tutorial-other-button = Odbiorcę spoza listy dodasz przyciskiem "inni..."
+tutorial-other-dialog = Dodaj E-mail przyciskiem "dodaj kolejny e-mail" i zamknij okno krzyżykiem
+tutorial-file-picker = Wybierz pliki do wysłania
+tutorial-send = Kliknij ciężarówkę, aby wysłać E-mail
+tutorial-select-person = Wybierz osobę, którą chcesz dodać lub edytować
+tutorial-edit-person = Wpisz imię i E-mail wybranej osoby
+tutorial-save-settings = Zapisz zmiany przyciskiem "zapisz i zamknij"
+
+## nastavení
+
+settings-password-title = Proszę podać hasło do ustawień
+settings-password-placeholder = Hasło
+settings-password-check = sprawdź
+settings-config = konfiguracja
+saved-addresses = zapisane adresy
+settings-select-person = Proszę wybrać osobę do edycji danych
+discard-title = Czy na pewno wyjść bez zapisywania?
+discard-back = powrót do ustawień
+discard-leave = wyjdź bez zapisywania
+invalid-mail-title = Następujące osoby mają nieprawidłowy e-mail
+invalid-mail-hint = Popraw lub usuń je
+person-name = imię i nazwisko
+person-mail = e-mail
+person-certificate = certyfikat
+person-certificate-placeholder = ścieżka do certyfikatu S/MIME
+addresses-title = zapisane adresy do podpowiedzi
+addresses-block = zablokuj
+addresses-unblock = odblokuj
+addresses-delete = usuń
+
+## konfigurace
+
+config-title = edycja pliku konfiguracyjnego
+config-locale = język:
+config-sender-name = Nazwa nadawcy:
+config-sender-mail = E-mail nadawcy:
+config-sender-password = hasło nadawcy:
+config-sender-auth = logowanie nadawcy:
+config-auth-password = hasło
+config-auth-oauth2 = oauth2
+config-oauth-client-id = oauth2 client id:
+config-oauth-client-secret = oauth2 client secret:
+config-oauth-refresh-token = oauth2 refresh token:
+config-oauth-token-endpoint = oauth2 token endpoint:
+config-title-template = temat e-maila:
+config-body = treść e-maila:
+config-smtp-transport = serwer smtp:
+config-feedback-mail = e-mail dla opinii:
+config-feedback-recepient = odbiorca opinii:
+config-feedback-subject = temat opinii:
+config-settings-password = hasło ustawień:
+config-watch-directory = obserwowany folder:
+config-watch-pattern = maska plików:
+config-watch-max-age = wiek plików (godziny, 0 = bez limitu):
+config-watch-archive = archiwizuj wysłane:
+config-post-send-action = po wysłaniu plików:
+config-post-send-leave = pozostaw
+config-post-send-move = przenieś do archiwum
+config-post-send-rename = zmień nazwę
+config-archive-directory = folder archiwum:
+config-plate-pattern = wzór nr rejestracyjnego:
+config-job-number-pattern = wzór numeru zlecenia:
+config-merge-file-name = nazwa połączonego PDF:
+config-attachment-name = nazwa załącznika:
+config-keep-recipients = zachowaj odbiorców po wysłaniu:
+config-author-in-sender-name = nazwisko mechanika w nazwie nadawcy:
+config-profile-selector = wybór konta na ekranie głównym:
+config-delivery-status = żądaj potwierdzenia doręczenia (DSN):
+config-read-receipt = żądaj potwierdzenia przeczytania:
+config-dkim-selector = DKIM selektor:
+config-dkim-domain = DKIM domena:
+config-dkim-private-key = DKIM klucz prywatny (ścieżka):
+config-smime-certificate = S/MIME certyfikat PKCS#12 (ścieżka):
+config-smime-password = S/MIME hasło certyfikatu:
+config-smime-encrypt = S/MIME szyfrowanie dla odbiorców:
+config-imap-host = serwer IMAP dla potwierdzeń:
+config-imap-port = IMAP port:
+config-imap-username = IMAP użytkownik:
+config-imap-password = IMAP hasło:
+config-imap-folder = folder IMAP z potwierdzeniami:
+config-imap-sent-folder = folder IMAP na kopie wysłanych (puste = nie zapisuj):
+config-imap-tls = IMAP szyfrowane połączenie (TLS):
+config-default-profile = domyślne konto nadawcy:
+config-profile = kolejne konto nadawcy
+config-profile-remove = usuń konto
+config-profile-label = nazwa konta:
+config-profile-add = dodaj konto nadawcy
+config-profile-new-label = konto { $number }
+
+## chyby konfigurace a certifikátů
+
+dkim-missing-selector = DKIM: brak selektora
+dkim-missing-domain = DKIM: brak domeny
+dkim-invalid-key = DKIM: klucz prywatny nie jest prawidłowym kluczem RSA w formacie PEM
+dkim-unreadable-key = DKIM: nie można odczytać pliku z kluczem prywatnym
+smime-encrypt-needs-certificate = S/MIME: szyfrowanie wymaga certyfikatu nadawcy
+smime-invalid-certificate = S/MIME: nie można otworzyć certyfikatu, sprawdź plik PKCS#12 i hasło
+smime-unreadable-certificate = S/MIME: nie można odczytać pliku z certyfikatem
+recipient-missing-certificate = odbiorca nie ma zapisanego certyfikatu
+recipient-unreadable-certificate = nie można odczytać certyfikatu odbiorcy
+recipient-other-without-certificate = do jednorazowego adresu nie można przypisać certyfikatu, dodaj odbiorcę w ustawieniach
+archive-directory-missing = Przeniesienie po wysłaniu: nie podano folderu archiwum
@@ -0,0 +1,190 @@
+## hlavní obrazovka
+
+settings = nastavenia
+feedback = hlásenie chýb a nápady na vylepšenie
+manual = návod na použitie
+pick-files = výber súborov
+add-files = pridať ďalšie
+remove = odstrániť
+send = odoslať
+save = uložiť
+save-and-close = uložiť a zavrieť
+close-without-saving = zavrieť bez uloženia
+yes = áno
+no = nie
+other-recipients = ostatní...
+last-recipients = poslední príjemcovia
+author-select = kto odosiela?
+report-new = nový protokol
+report-attach = priložiť
+file-already-selected = súbor { $name } je už vybraný
+file-unreadable = súbor { $name } nie je možné prečítať
+thumbnails-unavailable = náhľady PDF nie sú dostupné, chýba knižnica pdfium
+merge-pdfs-on = PDF budú zlúčené do jedného
+merge-pdfs-off = zlúčiť PDF do jedného
+vehicle-vin = VIN
+vehicle-plate = EČV
+vehicle-job-number = zákazka
+vehicle-not-found = nenájdené
+
+## odeslání
+
+send-confirm-title = naozaj odoslať?
+send-confirm-author = odosiela: { $name }
+send-confirm-profile = účet: { $label } ({ $mail })
+receipt-delivery-status = doručenka
+receipt-read = potvrdenie o prečítaní
+validation-title = E-mail nie je možné odoslať
+validation-no-file = nie je vybraný žiadny súbor
+validation-no-recipients = nie je vybraný žiadny príjemca
+send-error-title = E-mail sa nepodarilo odoslať
+send-error-retry = súbory aj príjemcovia zostali vybraní, odoslanie môžete zopakovať
+sent-copy-title = E-mail bol odoslaný, ale kópia nebola uložená
+sent-copy-retry = uloženie sa bude automaticky opakovať
+imap-no-connection = IMAP server nie je dostupný
+imap-rejected = IMAP server kópiu odmietol: { $reason }
+imap-invalid-response = IMAP server odpovedal neplatne
+mailbox-failed-title = E-mail nebol doručený
+
+## ostatní příjemci
+
+other-title = zadajte prosím e-mailové adresy
+other-add = pridať ďalší e-mail
+other-placeholder = Zadajte prosím e-mail
+other-promote = do zoznamu
+address-missing-parts = chýba časť pred alebo za @
+address-unbalanced = neuzavretá zátvorka < >
+address-invalid-user = neplatná časť pred @
+address-invalid-domain = neplatná doména za @
+address-invalid-characters = nepovolené znaky
+promote-title = uložiť adresu do zoznamu
+promote-mail = E-mail:
+promote-name = meno:
+promote-category = kategória:
+promote-mechanics = mechanici
+promote-technics = technici
+promote-wrong-password = nesprávne heslo
+promote-missing-name = zadajte prosím meno
+promote-category-full = v kategórii nie je voľné miesto
+promote-save-failed = zoznam sa nepodarilo uložiť
+
+## zpětná vazba
+
+feedback-placeholder = Zadajte prosím správu pre vývojárov
+feedback-sent = Spätná väzba bola odoslaná, ďakujeme!
+feedback-failed = Nepodarilo sa odoslať spätnú väzbu.
+feedback-contact-admin = Kontaktujte prosím administrátora!
+
+## návody
+
+manual-title = Návod na použitie
+settings-manual-title = Návod na použitie nastavení
+
+## sprievodca
+
+tutorial-start = spustiť sprievodcu
+tutorial-skip = preskočiť sprievodcu
+tutorial-progress = krok { $step } z { $count }
+tutorial-recipients = Vyberte príjemcu kliknutím na jeho meno
+tutorial-other-button = Príjemcu mimo zoznamu pridáte tlačidlom "ostatní..."
+tutorial-other-dialog = Pridajte E-mail tlačidlom "pridať ďalší e-mail" a okno zavrite krížikom
+tutorial-file-picker = Vyberte súbory na odoslanie
+tutorial-send = Kliknite na nákladiak a E-mail odošlite
+tutorial-select-person = Vyberte osobu, ktorú chcete pridať alebo upraviť
+tutorial-edit-person = Vyplňte meno a E-mail vybranej osoby
+tutorial-save-settings = Zmeny uložte tlačidlom "uložiť a zavrieť"
+
+## nastavení
+
+settings-password-title = Zadajte prosím heslo pre vstup do nastavení
+settings-password-placeholder = Heslo
+settings-password-check = overiť
+settings-config = konfigurácia
+saved-addresses = uložené adresy
+settings-select-person = Vyberte prosím osobu na úpravu údajov
+discard-title = Naozaj chcete odísť bez uloženia?
+discard-back = návrat späť do nastavení
+discard-leave = odísť bez uloženia
+invalid-mail-title = Nasledujúce osoby majú neplatný e-mail
+invalid-mail-hint = Upravte ich alebo zmažte
+person-name = meno
+person-mail = e-mail
+person-certificate = certifikát
+person-certificate-placeholder = cesta k certifikátu S/MIME
+addresses-title = uložené adresy pre našepkávanie
+addresses-block = blokovať
+addresses-unblock = odblokovať
+addresses-delete = zmazať
+
+## konfigurace
+
+config-title = úprava konfiguračného súboru
+config-locale = jazyk:
+config-sender-name = Meno odosielateľa:
+config-sender-mail = E-mail odosielateľa:
+config-sender-password = heslo odosielateľa:
+config-sender-auth = prihlásenie odosielateľa:
+config-auth-password = heslo
+config-auth-oauth2 = oauth2
+config-oauth-client-id = oauth2 client id:
+config-oauth-client-secret = oauth2 client secret:
+config-oauth-refresh-token = oauth2 refresh token:
+config-oauth-token-endpoint = oauth2 token endpoint:
+config-title-template = predmet e-mailu:
+config-body = text e-mailu:
+config-smtp-transport = smtp transport:
+config-feedback-mail = feedback e-mail:
+config-feedback-recepient = feedback príjemca:
+config-feedback-subject = feedback predmet:
+config-settings-password = heslo nastavení:
+config-watch-directory = sledovaný priečinok:
+config-watch-pattern = maska súborov:
+config-watch-max-age = vek súborov (hodín, 0 = bez limitu):
+config-watch-archive = archivovať odoslané:
+config-post-send-action = po odoslaní súborov:
+config-post-send-leave = ponechať
+config-post-send-move = presunúť do archívu
+config-post-send-rename = premenovať
+config-archive-directory = archívny priečinok:
+config-plate-pattern = vzor EČV:
+config-job-number-pattern = vzor čísla zákazky:
+config-merge-file-name = názov zlúčeného PDF:
+config-attachment-name = názov prílohy:
+config-keep-recipients = ponechať príjemcov po odoslaní:
+config-author-in-sender-name = meno mechanika v mene odosielateľa:
+config-profile-selector = výber účtu na hlavnej obrazovke:
+config-delivery-status = žiadať o doručenku (DSN):
+config-read-receipt = žiadať o potvrdenie o prečítaní:
+config-dkim-selector = DKIM selektor:
+config-dkim-domain = DKIM doména:
+config-dkim-private-key = DKIM súkromný kľúč (cesta):
+config-smime-certificate = S/MIME certifikát PKCS#12 (cesta):
+config-smime-password = S/MIME heslo certifikátu:
+config-smime-encrypt = S/MIME šifrovanie pre príjemcov:
+config-imap-host = IMAP server pre doručenky:
+config-imap-port = IMAP port:
+config-imap-username = IMAP používateľ:
+config-imap-password = IMAP heslo:
+config-imap-folder = IMAP priečinok s doručenkami:
+config-imap-sent-folder = IMAP priečinok pre kópie odoslaných (prázdne = neukladať):
+config-imap-tls = IMAP šifrované spojenie (TLS):
+config-default-profile = predvolený účet odosielateľa:
+config-profile = ďalší účet odosielateľa
+config-profile-remove = odstrániť účet
+config-profile-label = názov účtu:
+config-profile-add = pridať účet odosielateľa
+config-profile-new-label = účet { $number }
+
+## chyby konfigurace a certifikátů
+
+dkim-missing-selector = DKIM: chýba selektor
+dkim-missing-domain = DKIM: chýba doména
+dkim-invalid-key = DKIM: súkromný kľúč nie je platný RSA kľúč vo formáte PEM
+dkim-unreadable-key = DKIM: súbor so súkromným kľúčom nie je možné prečítať
+smime-encrypt-needs-certificate = S/MIME: šifrovanie vyžaduje certifikát odosielateľa
+smime-invalid-certificate = S/MIME: certifikát nie je možné otvoriť, skontrolujte súbor PKCS#12 a heslo
+smime-unreadable-certificate = S/MIME: súbor s certifikátom nie je možné prečítať
+recipient-missing-certificate = príjemca nemá uložený certifikát
+recipient-unreadable-certificate = certifikát príjemcu nie je možné prečítať
+recipient-other-without-certificate = jednorazovej adrese nie je možné priradiť certifikát, pridajte príjemcu v nastaveniach
+archive-directory-missing = Presun po odoslaní: nie je zadaný priečinok archívu
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::mail_list_utils::Person;
+
+//---------------------------
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UsedAddress {
+    pub name: String,
+    pub mail: String,
+    pub count: u32,
+    pub last_used: DateTime<Local>,
+    pub blocked: bool,
+}
+
+impl UsedAddress {
+    //frequent addresses are preferred, the score fades out with age
+    fn score(&self) -> f64 {
+        let days = (Local::now() - self.last_used).num_days().max(0) as f64;
+
+        self.count as f64 / (1.0 + days / 30.0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct AddressSuggestions {
+    addresses: Vec<UsedAddress>,
+}
+
+impl AddressSuggestions {
+    pub fn load_suggestions() -> AddressSuggestions {
+        match std::fs::read_to_string("address_suggestions.ron") {
+            Ok(ron_string) => ron::de::from_str(&ron_string).unwrap_or_default(),
+            Err(_) => AddressSuggestions::default(),
+        }
+    }
+
+    pub fn save_suggestions(&self) {
+        let ron_string =
+            ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).unwrap();
+
+        std::fs::write("address_suggestions.ron", ron_string).unwrap();
+    }
+
+    pub fn record(&mut self, people: &[Person]) {
+        for person in people {
+            match self
+                .addresses
+                .iter_mut()
+                .find(|address| address.mail.eq_ignore_ascii_case(&person.mail))
+            {
+                Some(address) => {
+                    address.name = person.name.clone();
+                    address.count += 1;
+                    address.last_used = Local::now();
+                }
+                None => self.addresses.push(UsedAddress {
+                    name: person.name.clone(),
+                    mail: person.mail.clone(),
+                    count: 1,
+                    last_used: Local::now(),
+                    blocked: false,
+                }),
+            }
+        }
+    }
+
+    pub fn suggestions(&self) -> Vec<&UsedAddress> {
+        let mut suggestions: Vec<&UsedAddress> = self
+            .addresses
+            .iter()
+            .filter(|address| !address.blocked)
+            .collect();
+
+        suggestions.sort_by(|a, b| b.score().total_cmp(&a.score()));
+
+        suggestions
+    }
+
+    pub fn addresses(&self) -> &[UsedAddress] {
+        &self.addresses
+    }
+
+    //addresses are looked up by mail, list order changes with every send
+    pub fn purge(&mut self, mail: &str) {
+        self.addresses
+            .retain(|address| !address.mail.eq_ignore_ascii_case(mail));
+    }
+
+    //blocked address stays stored so it is not suggested again after next send
+    pub fn toggle_block(&mut self, mail: &str) {
+        if let Some(address) = self
+            .addresses
+            .iter_mut()
+            .find(|address| address.mail.eq_ignore_ascii_case(mail))
+        {
+            address.blocked = !address.blocked;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::Duration;
+
+    fn person(name: &str, mail: &str) -> Person {
+        Person {
+            name: name.to_string(),
+            mail: mail.to_string(),
+            certificate: String::new(),
+        }
+    }
+
+    fn suggested_mails(suggestions: &AddressSuggestions) -> Vec<&str> {
+        suggestions
+            .suggestions()
+            .iter()
+            .map(|address| address.mail.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn record_counts_same_address_once() {
+        let mut suggestions = AddressSuggestions::default();
+
+        suggestions.record(&[person("Dílna", "dilna@servis.cz")]);
+        suggestions.record(&[person("Dílna Postřižín", "DILNA@servis.cz")]);
+
+        assert_eq!(suggestions.addresses().len(), 1);
+        assert_eq!(suggestions.addresses()[0].count, 2);
+        assert_eq!(suggestions.addresses()[0].name, "Dílna Postřižín");
+    }
+
+    #[test]
+    fn frequent_and_recent_addresses_come_first() {
+        let mut suggestions = AddressSuggestions::default();
+
+        suggestions.record(&[person("Sklad", "sklad@servis.cz")]);
+        suggestions.record(&[
+            person("Dílna", "dilna@servis.cz"),
+            person("Účtárna", "ucetni@servis.cz"),
+        ]);
+        suggestions.record(&[person("Účtárna", "ucetni@servis.cz")]);
+        suggestions.record(&[person("Účtárna", "ucetni@servis.cz")]);
+
+        //three uses half a year ago score less than one use today
+        suggestions.addresses[2].last_used = Local::now() - Duration::days(180);
+
+        assert_eq!(
+            suggested_mails(&suggestions),
+            ["sklad@servis.cz", "dilna@servis.cz", "ucetni@servis.cz"]
+        );
+    }
+
+    #[test]
+    fn blocked_and_purged_addresses_are_not_suggested() {
+        let mut suggestions = AddressSuggestions::default();
+
+        suggestions.record(&[
+            person("Dílna", "dilna@servis.cz"),
+            person("Sklad", "sklad@servis.cz"),
+            person("Účtárna", "ucetni@servis.cz"),
+        ]);
+        suggestions.record(&[person("Účtárna", "ucetni@servis.cz")]);
+
+        suggestions.toggle_block("Sklad@servis.cz");
+        suggestions.purge("dilna@servis.cz");
+
+        assert_eq!(suggested_mails(&suggestions), ["ucetni@servis.cz"]);
+        assert_eq!(suggestions.addresses().len(), 2);
+        assert!(suggestions.addresses()[0].blocked);
+
+        suggestions.toggle_block("sklad@servis.cz");
+        assert_eq!(
+            suggested_mails(&suggestions),
+            ["ucetni@servis.cz", "sklad@servis.cz"]
+        );
+    }
+}
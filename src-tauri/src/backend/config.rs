@@ -1,6 +1,8 @@
 use lettre::transport::smtp::authentication::Credentials;
 use serde::{Deserialize, Serialize};
 
+use crate::backend::locale::{self, Locale};
+
 //---------------------------
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -14,6 +16,213 @@ pub struct Config {
     feedback_recepient: String,
     feedback_subject: String,
     settings_password: String,
+    #[serde(default)]
+    watch_directory: String,
+    #[serde(default = "default_watch_pattern")]
+    watch_pattern: String,
+    #[serde(default = "default_watch_max_age_hours")]
+    watch_max_age_hours: u64,
+    #[serde(default)]
+    watch_archive: bool,
+    #[serde(default)]
+    post_send_action: PostSendAction,
+    #[serde(default)]
+    archive_directory: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default = "default_plate_pattern")]
+    plate_pattern: String,
+    #[serde(default = "default_job_number_pattern")]
+    job_number_pattern: String,
+    #[serde(default = "default_merge_file_name")]
+    merge_file_name: String,
+    #[serde(default)]
+    attachment_name_template: String,
+    #[serde(default)]
+    keep_recipients: bool,
+    #[serde(default)]
+    author_in_sender_name: bool,
+    #[serde(default)]
+    sender_profiles: Vec<SenderProfile>,
+    #[serde(default)]
+    default_profile: usize,
+    #[serde(default)]
+    profile_selector: bool,
+    #[serde(default)]
+    sender_auth: SenderAuth,
+    #[serde(default)]
+    sender_oauth: OAuthSettings,
+    #[serde(default)]
+    dkim: DkimSettings,
+    #[serde(default)]
+    smime: SmimeSettings,
+    #[serde(default)]
+    delivery_status: bool,
+    #[serde(default)]
+    read_receipt: bool,
+    #[serde(default)]
+    imap: ImapSettings,
+    #[serde(default)]
+    locale: Locale,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum SenderAuth {
+    #[default]
+    Password,
+    OAuth2,
+}
+
+//access token for XOAUTH2 is obtained from refresh token, endpoint differs by provider
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OAuthSettings {
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: String,
+    pub token_endpoint: String,
+}
+
+impl Default for OAuthSettings {
+    fn default() -> Self {
+        OAuthSettings {
+            client_id: String::new(),
+            client_secret: String::new(),
+            refresh_token: String::new(),
+            token_endpoint: "https://oauth2.googleapis.com/token".to_string(),
+        }
+    }
+}
+
+fn edit_auth(auth: &mut SenderAuth, oauth: &mut OAuthSettings, field: &str, text: String) {
+    match field {
+        "auth" => {
+            *auth = match text.as_str() {
+                "oauth2" => SenderAuth::OAuth2,
+                _ => SenderAuth::Password,
+            }
+        }
+        "client_id" => oauth.client_id = text,
+        "client_secret" => oauth.client_secret = text,
+        "refresh_token" => oauth.refresh_token = text,
+        "token_endpoint" => oauth.token_endpoint = text,
+        _ => {}
+    }
+}
+
+//another mailbox the mail can be sent from, profile 0 is always the main sender above
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SenderProfile {
+    pub label: String,
+    pub sender_name: String,
+    pub sender_mail: String,
+    pub sender_password: String,
+    pub smtp_transport: String,
+    pub title: String,
+    pub body: String,
+    #[serde(default)]
+    pub auth: SenderAuth,
+    #[serde(default)]
+    pub oauth: OAuthSettings,
+    #[serde(default)]
+    pub delivery_status: bool,
+    #[serde(default)]
+    pub read_receipt: bool,
+}
+
+impl SenderProfile {
+    pub fn credentials(&self) -> Credentials {
+        Credentials::new(self.sender_mail.clone(), self.sender_password.clone())
+    }
+}
+
+//signing is turned off while every field is empty
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DkimSettings {
+    pub selector: String,
+    pub domain: String,
+    pub private_key_path: String,
+}
+
+impl DkimSettings {
+    pub fn is_enabled(&self) -> bool {
+        !(self.selector.trim().is_empty()
+            && self.domain.trim().is_empty()
+            && self.private_key_path.trim().is_empty())
+    }
+}
+
+//mail is signed when certificate is set, encryption needs certificates of all recipients
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SmimeSettings {
+    pub certificate_path: String,
+    pub certificate_password: String,
+    pub encrypt: bool,
+}
+
+//mailbox of sender is checked for bounces and receipts while host is set
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ImapSettings {
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+    pub username: String,
+    pub password: String,
+    pub folder: String,
+    #[serde(default)]
+    pub sent_folder: String,
+}
+
+impl Default for ImapSettings {
+    fn default() -> Self {
+        ImapSettings {
+            host: String::new(),
+            port: 993,
+            tls: true,
+            username: String::new(),
+            password: String::new(),
+            folder: "INBOX".to_string(),
+            sent_folder: String::new(),
+        }
+    }
+}
+
+impl ImapSettings {
+    pub fn is_enabled(&self) -> bool {
+        !self.host.trim().is_empty()
+    }
+
+    //copy of sent mail is stored only when folder is set
+    pub fn saves_sent_copy(&self) -> bool {
+        self.is_enabled() && !self.sent_folder.trim().is_empty()
+    }
+}
+
+fn default_merge_file_name() -> String {
+    "{zakazka}_{datum}.pdf".to_string()
+}
+
+fn default_plate_pattern() -> String {
+    r"\b[0-9][A-Z][0-9A-Z] ?[0-9]{4}\b".to_string()
+}
+
+fn default_job_number_pattern() -> String {
+    r"(?i)zak[áa]zk[ay]\s*(?:č\.|číslo)?\s*:?\s*([0-9A-Z/-]+)".to_string()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum PostSendAction {
+    #[default]
+    Leave,
+    Move,
+    Rename,
+}
+
+fn default_watch_pattern() -> String {
+    "*.pdf".to_string()
+}
+
+fn default_watch_max_age_hours() -> u64 {
+    24
 }
 
 impl Config {
@@ -24,9 +233,22 @@ impl Config {
         std::fs::write("config.ron", ron_string).unwrap();
     }
     pub fn load_config() -> Config {
-        let ron_string = std::fs::read_to_string("config.ron").unwrap();
-        let result: Config = ron::de::from_str(&ron_string).unwrap();
-        result
+        Config::try_load_config().unwrap()
+    }
+    //command line reports missing or broken config instead of panicking
+    pub fn try_load_config() -> anyhow::Result<Config> {
+        let ron_string = std::fs::read_to_string("config.ron")?;
+        let result: Config = ron::de::from_str(&ron_string)?;
+        locale::set_locale(result.locale);
+        Ok(result)
+    }
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+    //ui switches language right away, not only after saving
+    pub fn save_locale(&mut self, text: String) {
+        self.locale = Locale::from_code(&text);
+        locale::set_locale(self.locale);
     }
     pub fn sender_name(&self) -> &str {
         &self.sender_name
@@ -46,8 +268,14 @@ impl Config {
     pub fn save_sender_password(&mut self, text: String) {
         self.sender_password = text;
     }
-    pub fn credentials(&self) -> Credentials {
-        Credentials::new(self.sender_mail.clone(), self.sender_password.clone())
+    pub fn sender_auth(&self) -> SenderAuth {
+        self.sender_auth
+    }
+    pub fn sender_oauth(&self) -> &OAuthSettings {
+        &self.sender_oauth
+    }
+    pub fn edit_sender_auth(&mut self, field: &str, text: String) {
+        edit_auth(&mut self.sender_auth, &mut self.sender_oauth, field, text);
     }
     pub fn title(&self) -> &str {
         &self.title
@@ -85,7 +313,253 @@ impl Config {
     pub fn save_settings_password(&mut self, text: String) {
         self.settings_password = text;
     }
+    pub fn watch_directory(&self) -> &str {
+        &self.watch_directory
+    }
+    pub fn save_watch_directory(&mut self, text: String) {
+        self.watch_directory = text;
+    }
+    pub fn watch_pattern(&self) -> &str {
+        &self.watch_pattern
+    }
+    pub fn save_watch_pattern(&mut self, text: String) {
+        self.watch_pattern = text;
+    }
+    pub fn watch_max_age_hours(&self) -> u64 {
+        self.watch_max_age_hours
+    }
+    pub fn save_watch_max_age_hours(&mut self, hours: u64) {
+        self.watch_max_age_hours = hours;
+    }
+    pub fn watch_archive(&self) -> bool {
+        self.watch_archive
+    }
+    pub fn save_watch_archive(&mut self, archive: bool) {
+        self.watch_archive = archive;
+    }
+    pub fn post_send_action(&self) -> PostSendAction {
+        self.post_send_action
+    }
+    pub fn save_post_send_action(&mut self, action: PostSendAction) {
+        self.post_send_action = action;
+    }
+    pub fn archive_directory(&self) -> &str {
+        &self.archive_directory
+    }
+    pub fn save_archive_directory(&mut self, text: String) {
+        self.archive_directory = text;
+    }
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+    pub fn save_body(&mut self, text: String) {
+        self.body = text;
+    }
+    pub fn plate_pattern(&self) -> &str {
+        &self.plate_pattern
+    }
+    pub fn save_plate_pattern(&mut self, text: String) {
+        self.plate_pattern = text;
+    }
+    pub fn job_number_pattern(&self) -> &str {
+        &self.job_number_pattern
+    }
+    pub fn save_job_number_pattern(&mut self, text: String) {
+        self.job_number_pattern = text;
+    }
+    pub fn merge_file_name(&self) -> &str {
+        &self.merge_file_name
+    }
+    pub fn save_merge_file_name(&mut self, text: String) {
+        self.merge_file_name = text;
+    }
+    pub fn attachment_name_template(&self) -> &str {
+        &self.attachment_name_template
+    }
+    pub fn save_attachment_name_template(&mut self, text: String) {
+        self.attachment_name_template = text;
+    }
+    pub fn keep_recipients(&self) -> bool {
+        self.keep_recipients
+    }
+    pub fn save_keep_recipients(&mut self, keep: bool) {
+        self.keep_recipients = keep;
+    }
+    pub fn author_in_sender_name(&self) -> bool {
+        self.author_in_sender_name
+    }
+    pub fn save_author_in_sender_name(&mut self, append: bool) {
+        self.author_in_sender_name = append;
+    }
+    pub fn delivery_status(&self) -> bool {
+        self.delivery_status
+    }
+    pub fn save_delivery_status(&mut self, request: bool) {
+        self.delivery_status = request;
+    }
+    pub fn read_receipt(&self) -> bool {
+        self.read_receipt
+    }
+    pub fn save_read_receipt(&mut self, request: bool) {
+        self.read_receipt = request;
+    }
+    pub fn sender_profiles(&self) -> &[SenderProfile] {
+        &self.sender_profiles
+    }
+    pub fn add_sender_profile(&mut self) {
+        self.sender_profiles.push(SenderProfile {
+            label: locale::tr_args(
+                "config-profile-new-label",
+                &[("number", &(self.sender_profiles.len() + 2).to_string())],
+            ),
+            smtp_transport: self.smtp_transport.clone(),
+            ..SenderProfile::default()
+        });
+    }
+    pub fn remove_sender_profile(&mut self, index: usize) {
+        if index < self.sender_profiles.len() {
+            self.sender_profiles.remove(index);
+        }
+
+        //default keeps pointing to the same profile, removed one falls back to main sender
+        let removed_id = index + 1;
+
+        if self.default_profile == removed_id {
+            self.default_profile = 0;
+        } else if self.default_profile > removed_id {
+            self.default_profile -= 1;
+        }
+    }
+    pub fn edit_sender_profile(&mut self, index: usize, field: &str, text: String) {
+        let Some(profile) = self.sender_profiles.get_mut(index) else {
+            return;
+        };
+
+        match field {
+            "label" => profile.label = text,
+            "sender_name" => profile.sender_name = text,
+            "sender_mail" => profile.sender_mail = text,
+            "sender_password" => profile.sender_password = text,
+            "smtp_transport" => profile.smtp_transport = text,
+            "title" => profile.title = text,
+            "body" => profile.body = text,
+            "delivery_status" => profile.delivery_status = text == "ano",
+            "read_receipt" => profile.read_receipt = text == "ano",
+            field => edit_auth(&mut profile.auth, &mut profile.oauth, field, text),
+        }
+    }
+    //main sender is first, unknown id falls back to it
+    pub fn profile(&self, id: usize) -> SenderProfile {
+        let main = SenderProfile {
+            label: self.sender_name.clone(),
+            sender_name: self.sender_name.clone(),
+            sender_mail: self.sender_mail.clone(),
+            sender_password: self.sender_password.clone(),
+            smtp_transport: self.smtp_transport.clone(),
+            title: self.title.clone(),
+            body: self.body.clone(),
+            auth: self.sender_auth,
+            oauth: self.sender_oauth.clone(),
+            delivery_status: self.delivery_status,
+            read_receipt: self.read_receipt,
+        };
+
+        match id
+            .checked_sub(1)
+            .and_then(|index| self.sender_profiles.get(index))
+        {
+            Some(profile) => SenderProfile {
+                title: if profile.title.is_empty() {
+                    main.title
+                } else {
+                    profile.title.clone()
+                },
+                body: if profile.body.is_empty() {
+                    main.body
+                } else {
+                    profile.body.clone()
+                },
+                ..profile.clone()
+            },
+            None => main,
+        }
+    }
+    pub fn profile_count(&self) -> usize {
+        self.sender_profiles.len() + 1
+    }
+    pub fn default_profile(&self) -> usize {
+        self.default_profile
+    }
+    pub fn save_default_profile(&mut self, id: usize) {
+        self.default_profile = id;
+    }
+    pub fn profile_selector(&self) -> bool {
+        self.profile_selector
+    }
+    pub fn save_profile_selector(&mut self, selector: bool) {
+        self.profile_selector = selector;
+    }
+    pub fn dkim(&self) -> &DkimSettings {
+        &self.dkim
+    }
+    pub fn edit_dkim(&mut self, field: &str, text: String) {
+        match field {
+            "selector" => self.dkim.selector = text,
+            "domain" => self.dkim.domain = text,
+            "private_key_path" => self.dkim.private_key_path = text,
+            _ => {}
+        }
+    }
+    pub fn smime(&self) -> &SmimeSettings {
+        &self.smime
+    }
+    pub fn edit_smime(&mut self, field: &str, text: String) {
+        match field {
+            "certificate_path" => self.smime.certificate_path = text,
+            "certificate_password" => self.smime.certificate_password = text,
+            "encrypt" => self.smime.encrypt = text == "ano",
+            _ => {}
+        }
+    }
+    pub fn imap(&self) -> &ImapSettings {
+        &self.imap
+    }
+    pub fn edit_imap(&mut self, field: &str, text: String) {
+        match field {
+            "host" => self.imap.host = text,
+            "port" => {
+                if let Ok(port) = text.trim().parse() {
+                    self.imap.port = port
+                }
+            }
+            "tls" => self.imap.tls = text == "ano",
+            "username" => self.imap.username = text,
+            "password" => self.imap.password = text,
+            "folder" => self.imap.folder = text,
+            "sent_folder" => self.imap.sent_folder = text,
+            _ => {}
+        }
+    }
     pub fn settings_password_check(&self, password: &str) -> bool {
         self.settings_password == password
     }
 }
+
+//main sender without optional settings, tests override what they need
+#[cfg(test)]
+pub fn test_config() -> Config {
+    ron::de::from_str(
+        r#"(
+            sender_name: "Dílna Postřižín",
+            sender_mail: "dilna@servis.cz",
+            sender_password: "",
+            title: "Protokol {spz}",
+            smtp_transport: "smtp.servis.cz",
+            feedback_mail: "vyvoj@servis.cz",
+            feedback_recepient: "Vývoj",
+            feedback_subject: "zpětná vazba",
+            settings_password: "",
+        )"#,
+    )
+    .unwrap()
+}
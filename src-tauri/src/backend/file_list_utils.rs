@@ -0,0 +1,191 @@
+use chrono::{DateTime, Local};
+use sha2::{Digest, Sha256};
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::backend::config::{Config, PostSendAction};
+use crate::backend::hot_folder;
+use crate::backend::mail_sender::MailSenderError;
+use crate::backend::thumbnail;
+use crate::backend::vehicle_info;
+
+//---------------------------
+
+const SENT_SUFFIX: &str = "_odeslano";
+
+#[derive(Debug, Clone)]
+pub struct SelectedFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<DateTime<Local>>,
+    pub hash: String,
+    pub text: Option<String>,
+    pub thumbnail: Option<String>,
+}
+
+impl SelectedFile {
+    pub fn load(path: PathBuf) -> Result<SelectedFile, MailSenderError> {
+        if !path.is_file() {
+            return Err(MailSenderError::InvalidFilePath);
+        }
+
+        let metadata = fs::metadata(&path).map_err(|_| MailSenderError::InvalidFilePath)?;
+        let content = fs::read(&path).map_err(|_| MailSenderError::InvalidFilePath)?;
+
+        let mut selected_file = SelectedFile {
+            size: metadata.len(),
+            modified: metadata.modified().ok().map(DateTime::<Local>::from),
+            hash: format!("{:x}", Sha256::digest(&content)),
+            text: vehicle_info::extract_text(&path),
+            thumbnail: None,
+            path,
+        };
+
+        if selected_file.is_pdf() {
+            selected_file.thumbnail = thumbnail::load_thumbnail(&content, &selected_file.hash);
+        }
+
+        Ok(selected_file)
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn size_text(&self) -> String {
+        let size = self.size as f64;
+
+        if size < 1024.0 {
+            format!("{} B", self.size)
+        } else if size < 1024.0 * 1024.0 {
+            format!("{:.1} kB", size / 1024.0)
+        } else {
+            format!("{:.1} MB", size / (1024.0 * 1024.0))
+        }
+    }
+
+    pub fn modified_text(&self) -> String {
+        match self.modified {
+            Some(modified) => modified.format("%d.%m.%Y %H:%M").to_string(),
+            None => "-".to_string(),
+        }
+    }
+
+    pub fn is_pdf(&self) -> bool {
+        self.path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("pdf"))
+    }
+
+    pub fn is_duplicate_of(&self, other: &SelectedFile) -> bool {
+        self.path == other.path || self.hash == other.hash
+    }
+
+    //moves or renames file after sending, path is updated to final location
+    pub fn apply_post_send_action(&mut self, config: &Config) -> io::Result<()> {
+        let new_path = match hot_folder::archive_directory(config, &self.path) {
+            Some(directory) => directory.join(self.file_name()),
+            None => match config.post_send_action() {
+                PostSendAction::Leave => return Ok(()),
+                PostSendAction::Move => {
+                    if config.archive_directory().is_empty() {
+                        return Err(io::Error::new(
+                            io::ErrorKind::NotFound,
+                            "archive directory is not set",
+                        ));
+                    }
+
+                    dated_directory(Path::new(config.archive_directory())).join(self.file_name())
+                }
+                PostSendAction::Rename => {
+                    let stem = self.path.file_stem().unwrap_or_default().to_string_lossy();
+
+                    let file_name = match self.path.extension() {
+                        Some(extension) => {
+                            format!("{stem}{SENT_SUFFIX}.{}", extension.to_string_lossy())
+                        }
+                        None => format!("{stem}{SENT_SUFFIX}"),
+                    };
+
+                    self.path.with_file_name(file_name)
+                }
+            },
+        };
+
+        let new_path = free_path(new_path);
+
+        if let Some(parent) = new_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        //rename doesn't work across drives
+        if fs::rename(&self.path, &new_path).is_err() {
+            fs::copy(&self.path, &new_path)?;
+            fs::remove_file(&self.path)?;
+        }
+
+        self.path = new_path;
+
+        Ok(())
+    }
+}
+
+//message key of reason why files can't be moved after send, None when action is usable
+pub fn post_send_problem(config: &Config) -> Option<&'static str> {
+    match config.post_send_action() {
+        PostSendAction::Move if config.archive_directory().trim().is_empty() => {
+            Some("archive-directory-missing")
+        }
+        _ => None,
+    }
+}
+
+pub fn dated_directory(directory: &Path) -> PathBuf {
+    directory.join(Local::now().format("%Y-%m-%d").to_string())
+}
+
+//adds " (n)" to file name until nothing exists on the path
+fn free_path(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let extension = path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+
+    (1..)
+        .map(|number| path.with_file_name(format!("{stem} ({number}){extension}")))
+        .find(|path| !path.exists())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_path_numbers_colliding_names() {
+        let directory = std::env::temp_dir().join("mailsender_free_path");
+        fs::remove_dir_all(&directory).ok();
+        fs::create_dir_all(&directory).unwrap();
+
+        let protokol = directory.join("protokol.pdf");
+        assert_eq!(free_path(protokol.clone()), protokol);
+
+        fs::write(&protokol, "1").unwrap();
+        fs::write(directory.join("protokol (1).pdf"), "2").unwrap();
+        assert_eq!(free_path(protokol), directory.join("protokol (2).pdf"));
+
+        let readme = directory.join("README");
+        fs::write(&readme, "3").unwrap();
+        assert_eq!(free_path(readme), directory.join("README (1)"));
+    }
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Duration, Local};
+use glob::Pattern;
+
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::backend::config::Config;
+use crate::backend::file_list_utils::{dated_directory, SelectedFile};
+
+//---------------------------
+
+const ARCHIVE_DIRECTORY: &str = "odeslano";
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub path: PathBuf,
+    pub modified: DateTime<Local>,
+}
+
+impl Report {
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn modified_text(&self) -> String {
+        self.modified.format("%d.%m.%Y %H:%M").to_string()
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct HotFolder {
+    reports: Vec<Report>,
+}
+
+impl HotFolder {
+    //finds new reports in watched directory, already selected files are skipped
+    pub fn scan(&mut self, config: &Config, selected_files: &[SelectedFile]) -> &[Report] {
+        self.reports.clear();
+
+        if config.watch_directory().is_empty() {
+            return &self.reports;
+        }
+
+        let Ok(pattern) = Pattern::new(config.watch_pattern()) else {
+            return &self.reports;
+        };
+
+        let Ok(entries) = fs::read_dir(config.watch_directory()) else {
+            return &self.reports;
+        };
+
+        let oldest = match config.watch_max_age_hours() {
+            0 => None,
+            hours => Some(Local::now() - Duration::hours(hours as i64)),
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if !path.is_file()
+                || !pattern.matches(&entry.file_name().to_string_lossy())
+                || selected_files.iter().any(|file| file.path == path)
+            {
+                continue;
+            }
+
+            let Some(modified) = entry
+                .metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .map(DateTime::<Local>::from)
+            else {
+                continue;
+            };
+
+            if oldest.is_some_and(|oldest| modified < oldest) {
+                continue;
+            }
+
+            self.reports.push(Report { path, modified });
+        }
+
+        self.reports.sort_by_key(|report| Reverse(report.modified));
+
+        &self.reports
+    }
+
+    //cards are addressed by path, list order changes whenever new report arrives
+    pub fn report(&self, path: &Path) -> Option<Report> {
+        self.reports.iter().find(|report| report.path == path).cloned()
+    }
+}
+
+//dated archive subfolder for sent files which came from watched directory
+pub fn archive_directory(config: &Config, path: &Path) -> Option<PathBuf> {
+    if !config.watch_archive() || config.watch_directory().is_empty() {
+        return None;
+    }
+
+    let watch_directory = Path::new(config.watch_directory());
+
+    if path.parent() != Some(watch_directory) {
+        return None;
+    }
+
+    Some(dated_directory(&watch_directory.join(ARCHIVE_DIRECTORY)))
+}
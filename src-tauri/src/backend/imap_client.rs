@@ -0,0 +1,348 @@
+use chrono::NaiveDate;
+use imap_proto::{AttributeValue, MailboxDatum, Response, ResponseCode, Status};
+use native_tls::TlsConnector;
+use thiserror::Error;
+
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use crate::backend::config::ImapSettings;
+
+//---------------------------
+
+const IMAP_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Error, Debug)]
+pub enum ImapError {
+    #[error("couldn't connect to imap server")]
+    NoConnection,
+
+    #[error("imap server rejected command: {0}")]
+    Rejected(String),
+
+    #[error("invalid imap response")]
+    InvalidResponse,
+}
+
+trait Stream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Stream for T {}
+
+//only commands needed for reading and storing mail are supported
+pub struct ImapClient {
+    stream: Box<dyn Stream>,
+    buffer: Vec<u8>,
+    next_tag: u32,
+}
+
+impl ImapClient {
+    pub fn connect(settings: &ImapSettings) -> Result<ImapClient, ImapError> {
+        //unreachable server mustn't hang for system tcp timeout
+        let tcp_stream = (settings.host.trim(), settings.port)
+            .to_socket_addrs()
+            .map_err(|_| ImapError::NoConnection)?
+            .find_map(|address| TcpStream::connect_timeout(&address, IMAP_TIMEOUT).ok())
+            .ok_or(ImapError::NoConnection)?;
+
+        tcp_stream.set_read_timeout(Some(IMAP_TIMEOUT)).ok();
+        tcp_stream.set_write_timeout(Some(IMAP_TIMEOUT)).ok();
+
+        let stream: Box<dyn Stream> = if settings.tls {
+            Box::new(
+                TlsConnector::new()
+                    .map_err(|_| ImapError::NoConnection)?
+                    .connect(settings.host.trim(), tcp_stream)
+                    .map_err(|_| ImapError::NoConnection)?,
+            )
+        } else {
+            Box::new(tcp_stream)
+        };
+
+        let mut client = ImapClient {
+            stream,
+            buffer: vec![],
+            next_tag: 0,
+        };
+
+        //server greets first
+        match client.read_response()? {
+            Response::Data {
+                status: Status::Ok, ..
+            } => {}
+            _ => return Err(ImapError::NoConnection),
+        }
+
+        client.command(&format!(
+            "LOGIN {} {}",
+            quote(&settings.username),
+            quote(&settings.password)
+        ))?;
+
+        Ok(client)
+    }
+
+    //returns uid validity, uids are compared only while it stays the same
+    pub fn select(&mut self, folder: &str) -> Result<Option<u32>, ImapError> {
+        let responses = self.command(&format!("SELECT {}", quote(folder)))?;
+
+        Ok(responses.into_iter().find_map(|response| match response {
+            Response::Data {
+                code: Some(ResponseCode::UidValidity(uid_validity)),
+                ..
+            } => Some(uid_validity),
+            _ => None,
+        }))
+    }
+
+    //uids of messages newer than last_uid, which arrived since given day
+    pub fn search_since(&mut self, last_uid: u32, since: NaiveDate) -> Result<Vec<u32>, ImapError> {
+        let responses = self.command(&format!(
+            "UID SEARCH UID {}:* SINCE {}",
+            last_uid + 1,
+            since.format("%d-%b-%Y")
+        ))?;
+
+        //range n:* always contains the last message, even when it is older
+        Ok(responses
+            .into_iter()
+            .flat_map(|response| match response {
+                Response::MailboxData(MailboxDatum::Search(uids)) => uids,
+                _ => vec![],
+            })
+            .filter(|uid| *uid > last_uid)
+            .collect())
+    }
+
+    //whole message is read without marking it as seen
+    pub fn fetch(&mut self, uid: u32) -> Result<Vec<u8>, ImapError> {
+        let responses = self.command(&format!("UID FETCH {uid} BODY.PEEK[]"))?;
+
+        responses
+            .into_iter()
+            .find_map(|response| match response {
+                Response::Fetch(_, attributes) => {
+                    attributes
+                        .into_iter()
+                        .find_map(|attribute| match attribute {
+                            AttributeValue::BodySection {
+                                data: Some(data), ..
+                            } => Some(data.into_owned()),
+                            _ => None,
+                        })
+                }
+                _ => None,
+            })
+            .ok_or(ImapError::InvalidResponse)
+    }
+
+    //mail is stored as literal, server has to accept its size before it is sent
+    pub fn append(&mut self, folder: &str, message: &[u8]) -> Result<(), ImapError> {
+        let tag = self.send_command(&format!(
+            "APPEND {} (\\Seen) {{{}}}",
+            quote(folder),
+            message.len()
+        ))?;
+
+        loop {
+            match self.read_response()? {
+                Response::Continue { .. } => break,
+                Response::Done {
+                    tag: done_tag,
+                    information,
+                    ..
+                } if done_tag.0 == tag => {
+                    return Err(ImapError::Rejected(
+                        information.unwrap_or_default().to_string(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        self.stream
+            .write_all(message)
+            .and_then(|_| self.stream.write_all(b"\r\n"))
+            .map_err(|_| ImapError::NoConnection)?;
+
+        self.finish_command(&tag).map(|_| ())
+    }
+
+    pub fn logout(mut self) {
+        self.command("LOGOUT").ok();
+    }
+
+    fn command(&mut self, command: &str) -> Result<Vec<Response<'static>>, ImapError> {
+        let tag = self.send_command(command)?;
+
+        self.finish_command(&tag)
+    }
+
+    fn send_command(&mut self, command: &str) -> Result<String, ImapError> {
+        self.next_tag += 1;
+        let tag = format!("a{}", self.next_tag);
+
+        self.stream
+            .write_all(format!("{tag} {command}\r\n").as_bytes())
+            .map_err(|_| ImapError::NoConnection)?;
+
+        Ok(tag)
+    }
+
+    //untagged responses are collected until command is done
+    fn finish_command(&mut self, tag: &str) -> Result<Vec<Response<'static>>, ImapError> {
+        let mut responses = vec![];
+
+        loop {
+            match self.read_response()? {
+                Response::Done {
+                    tag: done_tag,
+                    status,
+                    information,
+                    ..
+                } if done_tag.0 == tag => {
+                    return match status {
+                        Status::Ok => Ok(responses),
+                        _ => Err(ImapError::Rejected(
+                            information.unwrap_or_default().to_string(),
+                        )),
+                    };
+                }
+                response => responses.push(response),
+            }
+        }
+    }
+
+    fn read_response(&mut self) -> Result<Response<'static>, ImapError> {
+        loop {
+            match imap_proto::parser::parse_response(&self.buffer) {
+                Ok((rest, response)) => {
+                    let consumed = self.buffer.len() - rest.len();
+                    let response = response.into_owned();
+
+                    self.buffer.drain(..consumed);
+
+                    return Ok(response);
+                }
+                Err(error) if error.is_incomplete() => {
+                    let mut chunk = [0; 8192];
+
+                    let read = self
+                        .stream
+                        .read(&mut chunk)
+                        .map_err(|_| ImapError::NoConnection)?;
+
+                    if read == 0 {
+                        return Err(ImapError::NoConnection);
+                    }
+
+                    self.buffer.extend_from_slice(&chunk[..read]);
+                }
+                //untagged data which parser doesn't know is skipped
+                Err(_) => match self.buffer.windows(2).position(|end| end == b"\r\n") {
+                    Some(position) if self.buffer.starts_with(b"* ") => {
+                        self.buffer.drain(..position + 2);
+                    }
+                    _ => return Err(ImapError::InvalidResponse),
+                },
+            }
+        }
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{channel, Receiver};
+
+    //imap stand-in which accepts appends only to Sent folder and returns stored literal
+    fn imap_server() -> (u16, Receiver<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (sender, receiver) = channel();
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            stream.write_all(b"* OK stand-in ready\r\n").unwrap();
+
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    return;
+                }
+
+                let (tag, command) = line.trim_end().split_once(' ').unwrap();
+
+                if let Some(append) = command.strip_prefix("APPEND ") {
+                    if !append.starts_with("\"Sent\"") {
+                        stream
+                            .write_all(
+                                format!("{tag} NO [TRYCREATE] no such folder\r\n").as_bytes(),
+                            )
+                            .unwrap();
+                        continue;
+                    }
+
+                    let size: usize = append
+                        .rsplit_once('{')
+                        .unwrap()
+                        .1
+                        .trim_end_matches('}')
+                        .parse()
+                        .unwrap();
+                    stream.write_all(b"+ Ready for literal data\r\n").unwrap();
+
+                    let mut message = vec![0; size + 2];
+                    reader.read_exact(&mut message).unwrap();
+                    message.truncate(size);
+                    sender.send(message).unwrap();
+
+                    stream
+                        .write_all(format!("{tag} OK [APPENDUID 7 12] done\r\n").as_bytes())
+                        .unwrap();
+                } else {
+                    stream
+                        .write_all(format!("{tag} OK done\r\n").as_bytes())
+                        .unwrap();
+                }
+            }
+        });
+
+        (port, receiver)
+    }
+
+    #[test]
+    fn message_is_appended_unchanged() {
+        let (port, receiver) = imap_server();
+        let settings = ImapSettings {
+            host: "127.0.0.1".to_string(),
+            port,
+            tls: false,
+            username: "dilna@servis.cz".to_string(),
+            password: "heslo".to_string(),
+            folder: "INBOX".to_string(),
+            sent_folder: "Sent".to_string(),
+        };
+        let message = "From: dilna@servis.cz\r\nSubject: Protokol\r\n\r\nDobrý den\r\n".as_bytes();
+
+        let mut client = ImapClient::connect(&settings).unwrap();
+
+        assert!(matches!(
+            client.append("Odeslané", message),
+            Err(ImapError::Rejected(_))
+        ));
+
+        client.append("Sent", message).unwrap();
+        client.logout();
+
+        assert_eq!(receiver.recv().unwrap(), message);
+    }
+}
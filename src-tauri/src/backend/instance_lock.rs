@@ -0,0 +1,21 @@
+use std::fs::{File, OpenOptions};
+
+//---------------------------
+
+const LOCK_FILE: &str = "mailsender.lock";
+
+//running window keeps ron files in memory and overwrites them on save,
+//so command line may write them only while it holds this lock;
+//lock is released when the file is dropped or the process ends
+pub fn acquire() -> Option<File> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(LOCK_FILE)
+        .ok()?;
+
+    file.try_lock().ok()?;
+
+    Some(file)
+}
@@ -0,0 +1,167 @@
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+use std::sync::{LazyLock, RwLock};
+
+//---------------------------
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum Locale {
+    #[default]
+    Cs,
+    De,
+    Pl,
+    Sk,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 4] = [Locale::Cs, Locale::De, Locale::Pl, Locale::Sk];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::Cs => "cs",
+            Locale::De => "de",
+            Locale::Pl => "pl",
+            Locale::Sk => "sk",
+        }
+    }
+
+    //unknown code falls back to czech
+    pub fn from_code(code: &str) -> Locale {
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code() == code)
+            .unwrap_or_default()
+    }
+
+    //language name is shown in its own language, so everyone finds theirs
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::Cs => "čeština",
+            Locale::De => "Deutsch",
+            Locale::Pl => "polski",
+            Locale::Sk => "slovenčina",
+        }
+    }
+
+    fn resource(&self) -> &'static str {
+        match self {
+            Locale::Cs => include_str!("../../locales/cs.ftl"),
+            Locale::De => include_str!("../../locales/de.ftl"),
+            Locale::Pl => include_str!("../../locales/pl.ftl"),
+            Locale::Sk => include_str!("../../locales/sk.ftl"),
+        }
+    }
+}
+
+static BUNDLES: LazyLock<Vec<(Locale, FluentBundle<FluentResource>)>> = LazyLock::new(|| {
+    Locale::ALL
+        .into_iter()
+        .map(|locale| (locale, bundle(locale)))
+        .collect()
+});
+
+static CURRENT_LOCALE: RwLock<Locale> = RwLock::new(Locale::Cs);
+
+fn bundle(locale: Locale) -> FluentBundle<FluentResource> {
+    let language: LanguageIdentifier = locale.code().parse().unwrap();
+    let resource = FluentResource::try_new(locale.resource().to_string())
+        .unwrap_or_else(|(resource, _)| resource);
+
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    //isolation marks around arguments would end up in html as invisible characters
+    bundle.set_use_isolating(false);
+    bundle.add_resource_overriding(resource);
+
+    bundle
+}
+
+pub fn set_locale(locale: Locale) {
+    *CURRENT_LOCALE.write().unwrap() = locale;
+}
+
+pub fn current_locale() -> Locale {
+    *CURRENT_LOCALE.read().unwrap()
+}
+
+//text in current locale, missing translation falls back to czech and then to the key itself
+pub fn tr(key: &str) -> String {
+    translate(current_locale(), key, None)
+}
+
+pub fn tr_args(key: &str, args: &[(&str, &str)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, *value);
+    }
+
+    translate(current_locale(), key, Some(&fluent_args))
+}
+
+fn translate(locale: Locale, key: &str, args: Option<&FluentArgs>) -> String {
+    [locale, Locale::Cs]
+        .into_iter()
+        .find_map(|locale| {
+            let (_, bundle) = BUNDLES.iter().find(|(bundle_locale, _)| *bundle_locale == locale)?;
+            let pattern = bundle.get_message(key)?.value()?;
+
+            let mut errors = vec![];
+            Some(
+                bundle
+                    .format_pattern(pattern, args, &mut errors)
+                    .to_string(),
+            )
+        })
+        .unwrap_or_else(|| key.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use fluent_syntax::ast::Entry;
+    use fluent_syntax::parser;
+
+    fn message_keys(locale: Locale) -> Vec<String> {
+        parser::parse(locale.resource())
+            .unwrap()
+            .body
+            .into_iter()
+            .filter_map(|entry| match entry {
+                Entry::Message(message) => Some(message.id.name.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_locale_translates_all_czech_messages() {
+        let czech_keys = message_keys(Locale::Cs);
+
+        for locale in Locale::ALL {
+            let keys = message_keys(locale);
+            let missing: Vec<&String> = czech_keys.iter().filter(|key| !keys.contains(key)).collect();
+
+            assert!(missing.is_empty(), "{}: {missing:?}", locale.code());
+        }
+    }
+
+    #[test]
+    fn missing_message_falls_back() {
+        assert_eq!(translate(Locale::De, "settings", None), "Einstellungen");
+        assert_eq!(translate(Locale::De, "neexistuje", None), "neexistuje");
+    }
+
+    #[test]
+    fn arguments_are_inserted_without_isolation_marks() {
+        let mut args = FluentArgs::new();
+        args.set("name", "protokol.pdf");
+
+        assert_eq!(
+            translate(Locale::Cs, "file-already-selected", Some(&args)),
+            "soubor protokol.pdf už je vybrán"
+        );
+    }
+}
@@ -1,12 +1,19 @@
 use serde::{Deserialize, Serialize};
 use lettre::Address;
 
+use std::ops::Range;
+
 //---------------------------
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+pub const MECHANIC_IDS: Range<usize> = 0..24;
+pub const TECHNIC_IDS: Range<usize> = 24..29;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Person {
     pub name: String,
     pub mail: String,
+    #[serde(default)]
+    pub certificate: String, //path to s/mime certificate, needed only for encrypted mail
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -47,8 +54,12 @@ impl MailList {
     }
 
     pub fn load_list() -> MailList {
-        let ron_string = std::fs::read_to_string("mail_list.ron").unwrap();
-        ron::de::from_str(&ron_string).unwrap()
+        MailList::try_load_list().unwrap()
+    }
+
+    pub fn try_load_list() -> anyhow::Result<MailList> {
+        let ron_string = std::fs::read_to_string("mail_list.ron")?;
+        Ok(ron::de::from_str(&ron_string)?)
     }
 
     pub fn load_person(&self, id: usize) -> Option<Person> {
@@ -61,6 +72,7 @@ impl MailList {
             None => Person {
                 name: "".to_string(),
                 mail: "".to_string(),
+                certificate: "".to_string(),
             },
         };
 
@@ -75,6 +87,7 @@ impl MailList {
             None => Person {
                 name: "".to_string(),
                 mail: "".to_string(),
+                certificate: "".to_string(),
             },
         };
 
@@ -82,4 +95,28 @@ impl MailList {
 
         self.list[id] = Some(person);
     }
+
+    pub fn save_person_certificate(&mut self, id: usize, certificate: String) {
+        let mut person = match self.load_person(id) {
+            Some(person) => person,
+            None => Person {
+                name: "".to_string(),
+                mail: "".to_string(),
+                certificate: "".to_string(),
+            },
+        };
+
+        person.certificate = certificate;
+
+        self.list[id] = Some(person);
+    }
+
+    //puts person to first empty place of category, returns its id
+    pub fn add_person(&mut self, person: Person, ids: Range<usize>) -> Option<usize> {
+        let id = ids.into_iter().find(|id| self.list[*id].is_none())?;
+
+        self.list[id] = Some(person);
+
+        Some(id)
+    }
 }
@@ -1,23 +1,44 @@
 use anyhow::Result;
 
+use chrono::Local;
 
+
+use lettre::message::header::{Header, HeaderName, HeaderValue};
 use lettre::message::Mailbox;
-use lettre::message::{header::ContentType, Attachment, Body, MultiPart};
+use lettre::message::{header::ContentType, Attachment, Body, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::{Credentials, Mechanism};
+use lettre::transport::smtp::client::{SmtpConnection, TlsParameters};
+use lettre::transport::smtp::commands::{Data, Ehlo, Mail, Rcpt};
+use lettre::transport::smtp::extension::{
+    ClientId, MailBodyParameter, MailParameter, RcptParameter,
+};
+use lettre::transport::smtp::SUBMISSIONS_PORT;
 use lettre::{Address, Message, SmtpTransport, Transport};
 
 use std::fs;
-use std::path::PathBuf;
+use std::time::Duration;
 
 use tauri_plugin_dialog::FilePath;
 
 use thiserror::Error;
 
-use crate::backend::config::Config;
+use crate::backend::config::{Config, SenderAuth, SenderProfile};
+use crate::backend::dkim;
+use crate::backend::file_list_utils::SelectedFile;
 use crate::backend::mail_list_utils;
 use crate::backend::mail_list_utils::Person;
+use crate::backend::name_template;
+use crate::backend::oauth;
+use crate::backend::other_mail_utils;
+use crate::backend::pdf_merge;
+use crate::backend::send_history::{HistoryEntry, MailStatus, SentFile};
+use crate::backend::smime;
+use crate::backend::vehicle_info::VehicleInfo;
 
 //---------------------------
 
+const SMTP_TIMEOUT: Duration = Duration::from_secs(60);
+
 #[derive(Error, Debug)]
 pub enum MailSenderError {
     #[error("invalid file path")]
@@ -37,18 +58,75 @@ pub enum MailSenderError {
 
     #[error("Couldn't open a remote connection to gmail")]
     NoRemoteConnection,
+
+    #[error("couldn't merge pdf files")]
+    CouldntMergePdf,
+
+    #[error("couldn't get oauth2 access token")]
+    CouldntGetAccessToken,
+
+    #[error("couldn't sign message with dkim")]
+    CouldntSignMessage,
+
+    #[error("couldn't sign or encrypt message with s/mime")]
+    CouldntProtectMessage,
+
+    #[error("recipient {0} has no s/mime certificate")]
+    MissingRecipientCertificate(String),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ValidationProblem {
+    NoFile,
+    NoRecipients,
+    InvalidAddress { mail: String, reason: &'static str },
+    MissingCertificate { mail: String, reason: &'static str },
+}
+
+//names of picked files which weren't added
+#[derive(Debug, Default, PartialEq)]
+pub struct SkippedFiles {
+    pub duplicates: Vec<String>,
+    pub unreadable: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Recipient {
     pub name: String,
     pub mail: Address,
+    pub certificate: String,
 }
 
 #[derive(Default, Debug)]
 pub struct MailSender {
     people: Vec<Recipient>,
-    files: Option<Vec<PathBuf>>,
+    files: Vec<SelectedFile>,
+    vehicle: VehicleInfo,
+    edited_vehicle_fields: Vec<String>,
+    merge_pdfs: bool,
+    author: Option<Person>,
+    profile: Option<usize>,
+    delivery_status: Option<bool>,
+    read_receipt: Option<bool>,
+    subject: Option<String>,
+}
+
+//read receipt goes to sender mailbox, where it can be found with bounced mail
+#[derive(Debug, Clone)]
+struct DispositionNotificationTo(Address);
+
+impl Header for DispositionNotificationTo {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("Disposition-Notification-To")
+    }
+
+    fn parse(s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(DispositionNotificationTo(s.trim().parse()?))
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), self.0.to_string())
+    }
 }
 
 impl MailSender {
@@ -56,6 +134,7 @@ impl MailSender {
         let person_parsed = Recipient {
             name: person.name,
             mail: person.mail.parse().unwrap(),
+            certificate: person.certificate,
         };
 
         self.people.push(person_parsed);
@@ -63,62 +142,205 @@ impl MailSender {
         self
     }
 
+    //certificate may have changed in settings since person was selected
     pub fn remove_person(&mut self, person: Person) -> &mut Self {
-        let person_parsed = Recipient {
-            name: person.name,
-            mail: person.mail.parse().unwrap(),
-        };
+        let mail: Address = person.mail.parse().unwrap();
 
-        self.people.retain(|x| *x != person_parsed);
+        self.people
+            .retain(|recipient| recipient.name != person.name || recipient.mail != mail);
 
         self
     }
 
-    pub fn add_file(&mut self, vec_path: Vec<FilePath>) -> Result<(), MailSenderError> {
-        self.files = Some(vec![]);
+    pub fn has_person(&self, person: &Person) -> bool {
+        self.people.iter().any(|recipient| {
+            recipient.name == person.name && recipient.mail.to_string() == person.mail
+        })
+    }
+
+    //appends picked files, unreadable file doesn't stop the others from being added
+    pub fn add_file(&mut self, vec_path: Vec<FilePath>) -> SkippedFiles {
+        let mut skipped = SkippedFiles::default();
 
         for file in vec_path {
-            let path = file
+            let name = file
+                .as_path()
+                .and_then(|path| path.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let Ok(selected_file) = file
                 .into_path()
-                .map_err(|_| MailSenderError::InvalidFilePath)?;
+                .map_err(|_| MailSenderError::InvalidFilePath)
+                .and_then(SelectedFile::load)
+            else {
+                skipped.unreadable.push(name);
+                continue;
+            };
 
-            if !path.is_file() {
-                return Err(MailSenderError::InvalidFilePath);
+            if self
+                .files
+                .iter()
+                .any(|file| file.is_duplicate_of(&selected_file))
+            {
+                skipped.duplicates.push(selected_file.file_name());
+                continue;
             }
 
-            self.files.as_mut().unwrap().push(path);
+            self.files.push(selected_file);
         }
 
-        Ok(())
+        skipped
     }
 
-    pub fn send(
-        &mut self,
-        other_mail_list: Vec<mail_list_utils::Person>,
-        config: Config,
-    ) -> Result<()> {
-        other_mail_list.iter().for_each(|person| {
-            self.add_person(person.clone());
+    //values found only in removed file mustn't stay in subject, values typed by user are kept
+    pub fn remove_file(&mut self, index: usize, config: &Config) {
+        if index < self.files.len() {
+            self.files.remove(index);
+        }
+
+        let mut vehicle = VehicleInfo::default();
+
+        for field in &self.edited_vehicle_fields {
+            match field.as_str() {
+                "vin" => vehicle.vin = self.vehicle.vin.clone(),
+                "plate" => vehicle.plate = self.vehicle.plate.clone(),
+                "job_number" => vehicle.job_number = self.vehicle.job_number.clone(),
+                _ => {}
+            }
+        }
+
+        self.vehicle = vehicle;
+        self.detect_vehicle_info(config);
+    }
+
+    pub fn detect_vehicle_info(&mut self, config: &Config) {
+        for text in self.files.iter().filter_map(|file| file.text.as_ref()) {
+            self.vehicle.detect(text, config);
+        }
+    }
+
+    pub fn vehicle_info(&self) -> &VehicleInfo {
+        &self.vehicle
+    }
+
+    pub fn edit_vehicle_info(&mut self, field: &str, text: String) {
+        match field {
+            "vin" => self.vehicle.vin = text,
+            "plate" => self.vehicle.plate = text,
+            "job_number" => self.vehicle.job_number = text,
+            _ => return,
+        }
+
+        if !self
+            .edited_vehicle_fields
+            .iter()
+            .any(|edited| edited == field)
+        {
+            self.edited_vehicle_fields.push(field.to_string());
+        }
+    }
+
+    pub fn move_file_up(&mut self, index: usize) {
+        if index > 0 && index < self.files.len() {
+            self.files.swap(index - 1, index);
+        }
+    }
+
+    pub fn toggle_merge_pdfs(&mut self) {
+        self.merge_pdfs = !self.merge_pdfs;
+    }
+
+    pub fn merge_pdfs(&self) -> bool {
+        self.merge_pdfs
+    }
+
+    //merging makes sense only for more than one pdf
+    pub fn can_merge_pdfs(&self) -> bool {
+        self.files.iter().filter(|file| file.is_pdf()).count() > 1
+    }
+
+    fn merged_file_name(&self, config: &Config) -> String {
+        let file_name = name_template::sanitize_file_name(&name_template::fill_date(
+            &self.vehicle.fill_placeholders(config.merge_file_name()),
+        ));
+
+        let stem = if file_name.to_lowercase().ends_with(".pdf") {
+            &file_name[..file_name.len() - 4]
+        } else {
+            &file_name
+        };
+
+        //empty placeholders leave separators at the edges, e.g. "_2026-01-31"
+        let stem = stem.trim_matches(|character: char| {
+            character == '_' || character == '-' || character.is_whitespace()
         });
 
-        if self.people.is_empty() {
+        if stem.is_empty() {
+            format!("{}.pdf", name_template::fill_date("{datum}"))
+        } else {
+            format!("{stem}.pdf")
+        }
+    }
+
+    pub fn people(&self) -> &[Recipient] {
+        &self.people
+    }
+
+    pub fn files(&self) -> &[SelectedFile] {
+        &self.files
+    }
+
+    //ad-hoc addresses are not stored in people, so roster selection stays untouched
+    fn recipients(&self, other_mail_list: &[Person]) -> Result<Vec<Recipient>> {
+        let mut recipients: Vec<Recipient> = self.people.clone();
+
+        for person in other_mail_list {
+            recipients.push(Recipient {
+                name: person.name.clone(),
+                mail: person.mail.parse()?,
+                certificate: person.certificate.clone(),
+            });
+        }
+
+        Ok(recipients)
+    }
+
+    pub fn build_message(&self, other_mail_list: &[Person], config: &Config) -> Result<Message> {
+        let recipients = self.recipients(other_mail_list)?;
+
+        if recipients.is_empty() {
             return Err(MailSenderError::NoRecipients.into());
         }
-        if self.files.is_none() {
+        if self.files.is_empty() {
             return Err(MailSenderError::NoFile.into());
         }
 
         let mut message_builder = Message::builder();
 
+        let profile = config.profile(self.profile_id(config));
+
         //sender
-        message_builder = message_builder.from(Mailbox::new(
-            Some(config.sender_name().to_string()),
-            config.sender_mail().parse()?,
-        ));
+        let sender_name = match &self.author {
+            Some(author) if config.author_in_sender_name() => {
+                format!("{} ({})", profile.sender_name, author.name)
+            }
+            _ => profile.sender_name.clone(),
+        };
+
+        message_builder =
+            message_builder.from(Mailbox::new(Some(sender_name), profile.sender_mail.parse()?));
+
+        //replies go to mechanic who sent the mail
+        if let Some(author) = &self.author {
+            message_builder = message_builder.reply_to(Mailbox::new(
+                Some(author.name.clone()),
+                author.mail.parse()?,
+            ));
+        }
 
         //recipient
-        message_builder = self
-            .people
+        message_builder = recipients
             .iter()
             .fold(message_builder, |message_builder, recipient| {
                 message_builder.to(Mailbox::new(
@@ -127,18 +349,55 @@ impl MailSender {
                 ))
             });
 
+        if self.read_receipt(config) {
+            message_builder =
+                message_builder.header(DispositionNotificationTo(profile.sender_mail.parse()?));
+        }
+
         //subject
-        message_builder = message_builder.subject(config.title());
+        message_builder = message_builder.subject(self.subject(config));
 
-        //attachments
+        //bounces and receipts are matched to history by message id
+        message_builder = message_builder.message_id(None);
+
+        //body
         let mut attachment_multipart = MultiPart::mixed().build();
 
-        for file_path in self.files.as_ref().unwrap() {
+        if !profile.body.is_empty() {
+            attachment_multipart = attachment_multipart.singlepart(
+                SinglePart::plain(self.vehicle.fill_placeholders(&profile.body)),
+            );
+        }
+
+        //attachments
+        let merge_pdfs = self.merge_pdfs && self.can_merge_pdfs();
+        let mut pdf_files: Vec<Vec<u8>> = vec![];
+        let mut used_names: Vec<String> = vec![];
+
+        for selected_file in &self.files {
+            let file_path = &selected_file.path;
             let file = fs::read(file_path).map_err(|_| MailSenderError::InvalidFilePath)?;
 
+            if merge_pdfs && selected_file.is_pdf() {
+                pdf_files.push(file);
+                continue;
+            }
+
             let mime_type = mime_guess::from_path(file_path);
 
-            let file_name = file_path.file_name().unwrap().to_str().unwrap().to_string();
+            //only name in mail is changed, file on disk stays untouched
+            let file_name = name_template::unique_file_name(
+                name_template::attachment_file_name(
+                    config.attachment_name_template(),
+                    &self.vehicle,
+                    &profile.sender_name,
+                    &selected_file.file_name(),
+                    used_names.len() + 1,
+                ),
+                &used_names,
+            );
+
+            used_names.push(file_name.clone());
 
             attachment_multipart =
                 attachment_multipart
@@ -149,38 +408,144 @@ impl MailSender {
                     ));
         }
 
-        let message = message_builder.multipart(attachment_multipart);
+        if merge_pdfs {
+            attachment_multipart = attachment_multipart.singlepart(
+                Attachment::new(name_template::unique_file_name(
+                    self.merged_file_name(config),
+                    &used_names,
+                ))
+                .body(
+                    Body::new(pdf_merge::merge_pdfs(&pdf_files)?),
+                    ContentType::parse("application/pdf")?,
+                ),
+            );
+        }
+
+        let mut message = smime::build_message(
+            message_builder,
+            attachment_multipart,
+            &recipients,
+            config.smime(),
+        )?;
 
-        //get credentials
-        let creds = config.credentials();
+        dkim::sign_message(&mut message, config.dkim())?;
 
-        // open a remote connection to gmail
-        let mailer = SmtpTransport::relay(config.smtp_transport())
-            .map_err(|_| MailSenderError::NoRemoteConnection)?
-            .credentials(creds)
-            .build();
+        Ok(message)
+    }
+
+    pub fn send(
+        &mut self,
+        other_mail_list: Vec<mail_list_utils::Person>,
+        config: Config,
+    ) -> Result<(HistoryEntry, Message)> {
+        let message = self.build_message(&other_mail_list, &config)?;
+        let recipients = self.recipients(&other_mail_list)?;
+        let profile = config.profile(self.profile_id(&config));
 
         //send the email
-        mailer.send(&message.map_err(|_| MailSenderError::InvalidMessage)?)?;
+        let delivery_status = self.delivery_status(&config);
 
-        Ok(())
-    }
+        let delivery_status_unsupported = if delivery_status {
+            !send_with_delivery_status(&profile, &message)?
+        } else {
+            mailer(&profile)?.send(&message)?;
+            false
+        };
+
+        //move or rename sent files
+        let files = self
+            .files
+            .iter_mut()
+            .map(|file| {
+                let post_send_error = file
+                    .apply_post_send_action(&config)
+                    .err()
+                    .map(|error| error.to_string());
+
+                SentFile {
+                    name: file.file_name(),
+                    final_path: file.path.clone(),
+                    post_send_error,
+                }
+            })
+            .collect();
+
+        let history_entry = HistoryEntry {
+            time: Local::now(),
+            recipients: recipients
+                .iter()
+                .map(|recipient| recipient.mail.to_string())
+                .collect(),
+            files,
+            vehicle: self.vehicle.clone(),
+            roster_recipients: self.roster_people(),
+            other_recipients: other_mail_list,
+            author: self.author.clone(),
+            sender: profile.sender_mail,
+            delivery_status,
+            delivery_status_unsupported,
+            read_receipt: self.read_receipt(&config),
+            message_id: message_id(&message),
+            status: MailStatus::Sent,
+        };
 
-    pub fn file_is_valid(&self) -> bool {
-        self.files.is_some()
+        //sent message is returned, so exactly the same mail can be stored in sent folder
+        Ok((history_entry, message))
     }
 
-    pub fn person_list_is_valid(&self) -> bool {
-        !self.people.is_empty()
+    //lists everything which prevents sending, empty list means mail can be sent
+    pub fn validate(&self, other_mail_list: &[Person], config: &Config) -> Vec<ValidationProblem> {
+        let mut problems: Vec<ValidationProblem> = vec![];
+
+        if self.files.is_empty() {
+            problems.push(ValidationProblem::NoFile);
+        }
+
+        if self.people.is_empty() && other_mail_list.is_empty() {
+            problems.push(ValidationProblem::NoRecipients);
+        }
+
+        for person in other_mail_list {
+            if let Some(reason) = other_mail_utils::address_problem(&person.mail) {
+                problems.push(ValidationProblem::InvalidAddress {
+                    mail: person.mail.clone(),
+                    reason,
+                });
+            }
+        }
+
+        //encrypted mail can be read only by recipients with certificate
+        if config.smime().encrypt {
+            for person in self.roster_people() {
+                if let Some(reason) = smime::recipient_problem(&person.certificate) {
+                    problems.push(ValidationProblem::MissingCertificate {
+                        mail: person.mail.clone(),
+                        reason,
+                    });
+                }
+            }
+
+            //certificate can be stored only with people in settings
+            for person in other_mail_list {
+                problems.push(ValidationProblem::MissingCertificate {
+                    mail: person.mail.clone(),
+                    reason: "recipient-other-without-certificate",
+                });
+            }
+        }
+
+        problems
     }
 
-    pub fn send_feedback(text: String, config: Config) -> Result<()> {
+    pub fn build_feedback_message(text: String, config: &Config) -> Result<Message> {
         let mut message_builder = Message::builder();
 
+        let profile = config.profile(0);
+
         //sender
         message_builder = message_builder.from(Mailbox::new(
-            Some(config.sender_name().to_string()),
-            config.sender_mail().parse()?,
+            Some(profile.sender_name.clone()),
+            profile.sender_mail.parse()?,
         ));
 
         //recepient
@@ -193,25 +558,496 @@ impl MailSender {
         message_builder = message_builder.subject(config.feedback_subject());
 
         //body
-        let message = message_builder.body(text);
+        let mut message = message_builder
+            .body(text)
+            .map_err(|_| MailSenderError::InvalidMessage)?;
 
-        //get credentials
-        let creds = config.credentials();
+        dkim::sign_message(&mut message, config.dkim())?;
 
-        // open a remote connection to gmail
-        let mailer = SmtpTransport::relay(config.smtp_transport())
-            .map_err(|_| MailSenderError::NoRemoteConnection)?
-            .credentials(creds)
-            .build();
+        Ok(message)
+    }
+
+    pub fn send_feedback(text: String, config: Config) -> Result<()> {
+        let message = Self::build_feedback_message(text, &config)?;
 
         //send the email
-        mailer.send(&message.map_err(|_| MailSenderError::InvalidMessage)?)?;
+        mailer(&config.profile(0))?.send(&message)?;
 
         Ok(())
     }
 
     pub fn clear(&mut self) {
-        self.files = None;
+        self.clear_files();
         self.people.clear();
     }
+
+    //author is kept between mails, same mechanic usually sends more of them
+    pub fn set_author(&mut self, author: Option<Person>) {
+        self.author = author;
+    }
+
+    pub fn author(&self) -> Option<&Person> {
+        self.author.as_ref()
+    }
+
+    //admin default is used until profile is picked on main screen
+    pub fn set_profile(&mut self, profile: Option<usize>) {
+        self.profile = profile;
+    }
+
+    pub fn profile_id(&self, config: &Config) -> usize {
+        self.profile.unwrap_or(config.default_profile())
+    }
+
+    //picked profile keeps pointing to the same profile, removed one falls back to default
+    pub fn remove_profile(&mut self, removed_id: usize) {
+        self.profile = match self.profile {
+            Some(id) if id == removed_id => None,
+            Some(id) if id > removed_id => Some(id - 1),
+            profile => profile,
+        };
+    }
+
+    //subject from profile is used unless it is set here
+    pub fn set_subject(&mut self, subject: Option<String>) {
+        self.subject = subject;
+    }
+
+    pub fn subject(&self, config: &Config) -> String {
+        let profile = config.profile(self.profile_id(config));
+
+        self.vehicle
+            .fill_placeholders(self.subject.as_ref().unwrap_or(&profile.title))
+    }
+
+    //profile setting is used unless it was changed for this mail
+    pub fn delivery_status(&self, config: &Config) -> bool {
+        self.delivery_status
+            .unwrap_or(config.profile(self.profile_id(config)).delivery_status)
+    }
+
+    pub fn toggle_delivery_status(&mut self, config: &Config) {
+        self.delivery_status = Some(!self.delivery_status(config));
+    }
+
+    pub fn read_receipt(&self, config: &Config) -> bool {
+        self.read_receipt
+            .unwrap_or(config.profile(self.profile_id(config)).read_receipt)
+    }
+
+    pub fn toggle_read_receipt(&mut self, config: &Config) {
+        self.read_receipt = Some(!self.read_receipt(config));
+    }
+
+    //recipients are kept for next mail
+    pub fn clear_files(&mut self) {
+        self.files.clear();
+        self.vehicle = VehicleInfo::default();
+        self.edited_vehicle_fields.clear();
+        self.merge_pdfs = false;
+        self.delivery_status = None;
+        self.read_receipt = None;
+    }
+
+    pub fn roster_people(&self) -> Vec<Person> {
+        self.people
+            .iter()
+            .map(|recipient| Person {
+                name: recipient.name.clone(),
+                mail: recipient.mail.to_string(),
+                certificate: recipient.certificate.clone(),
+            })
+            .collect()
+    }
+
+    //drops recipients which are no longer in roster
+    pub fn retain_people(&mut self, roster: &[Person]) {
+        self.people.retain(|recipient| {
+            roster.iter().any(|person| {
+                recipient.name == person.name && recipient.mail.to_string() == person.mail
+            })
+        });
+    }
+}
+
+pub fn message_id(message: &Message) -> String {
+    message
+        .headers()
+        .get_raw("Message-ID")
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+//password or oauth2 access token, with mechanisms which can use it
+fn authentication(
+    profile: &SenderProfile,
+) -> Result<(Credentials, Vec<Mechanism>), MailSenderError> {
+    match profile.auth {
+        SenderAuth::Password => Ok((
+            profile.credentials(),
+            vec![Mechanism::Plain, Mechanism::Login],
+        )),
+        SenderAuth::OAuth2 => Ok((
+            Credentials::new(
+                profile.sender_mail.clone(),
+                oauth::access_token(&profile.oauth)?,
+            ),
+            vec![Mechanism::Xoauth2],
+        )),
+    }
+}
+
+//open a remote connection with password or oauth2 access token
+fn mailer(profile: &SenderProfile) -> Result<SmtpTransport, MailSenderError> {
+    let (credentials, mechanisms) = authentication(profile)?;
+
+    Ok(SmtpTransport::relay(&profile.smtp_transport)
+        .map_err(|_| MailSenderError::NoRemoteConnection)?
+        .credentials(credentials)
+        .authentication(mechanisms)
+        .build())
+}
+
+//transport can't add envelope parameters, so commands are sent one by one over implicit tls
+//returns false when server doesn't offer delivery status notifications
+fn send_with_delivery_status(profile: &SenderProfile, message: &Message) -> Result<bool> {
+    let tls_parameters = TlsParameters::new(profile.smtp_transport.clone())
+        .map_err(|_| MailSenderError::NoRemoteConnection)?;
+
+    let mut connection = SmtpConnection::connect(
+        (profile.smtp_transport.as_str(), SUBMISSIONS_PORT),
+        Some(SMTP_TIMEOUT),
+        &ClientId::default(),
+        Some(&tls_parameters),
+        None,
+    )
+    .map_err(|_| MailSenderError::NoRemoteConnection)?;
+
+    let (credentials, mechanisms) = authentication(profile)?;
+
+    send_over_connection(&mut connection, &credentials, &mechanisms, message)
+}
+
+fn send_over_connection(
+    connection: &mut SmtpConnection,
+    credentials: &Credentials,
+    mechanisms: &[Mechanism],
+    message: &Message,
+) -> Result<bool> {
+    let extensions: Vec<String> = connection
+        .command(Ehlo::new(ClientId::default()))?
+        .message()
+        .map(|line| line.to_ascii_uppercase())
+        .collect();
+    let supports_dsn = extensions.iter().any(|line| line == "DSN");
+    let supports_8bitmime = extensions.iter().any(|line| line == "8BITMIME");
+
+    connection.auth(mechanisms, credentials)?;
+
+    let email = message.formatted();
+    let envelope = message.envelope();
+
+    let mut mail_parameters: Vec<MailParameter> = vec![];
+    let mut rcpt_parameters: Vec<RcptParameter> = vec![];
+
+    if supports_dsn {
+        mail_parameters.push(MailParameter::Other {
+            keyword: "RET".to_string(),
+            value: Some("HDRS".to_string()),
+        });
+        rcpt_parameters.push(RcptParameter::Other {
+            keyword: "NOTIFY".to_string(),
+            value: Some("SUCCESS,FAILURE".to_string()),
+        });
+    }
+    if supports_8bitmime && !email.is_ascii() {
+        mail_parameters.push(MailParameter::Body(MailBodyParameter::EightBitMime));
+    }
+
+    connection.command(Mail::new(envelope.from().cloned(), mail_parameters))?;
+
+    for recipient in envelope.to() {
+        connection.command(Rcpt::new(recipient.clone(), rcpt_parameters.clone()))?;
+    }
+
+    connection.command(Data)?;
+    connection.message(&email)?;
+    connection.quit().ok();
+
+    Ok(supports_dsn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::backend::config::test_config;
+    use crate::backend::mailbox_poller;
+
+    use lettre::message::header::ContentTransferEncoding;
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+
+    fn temp_file(name: &str, content: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join("mailsender_add_file");
+        fs::create_dir_all(&directory).unwrap();
+
+        let path = directory.join(name);
+        fs::write(&path, content).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn duplicate_and_unreadable_files_are_skipped() {
+        let protokol = temp_file("protokol.txt", "zakázka 1");
+        let copy = temp_file("protokol_kopie.txt", "zakázka 1");
+        let faktura = temp_file("faktura.txt", "zakázka 2");
+        let missing = std::env::temp_dir().join("mailsender_add_file/chybi.txt");
+
+        let mut mail = MailSender::default();
+
+        let skipped = mail.add_file(vec![
+            FilePath::Path(protokol.clone()),
+            FilePath::Path(missing),
+            FilePath::Path(faktura),
+        ]);
+        assert_eq!(
+            skipped,
+            SkippedFiles {
+                duplicates: vec![],
+                unreadable: vec!["chybi.txt".to_string()],
+            }
+        );
+
+        //same path or same content under another name
+        let skipped = mail.add_file(vec![FilePath::Path(protokol), FilePath::Path(copy)]);
+        assert_eq!(
+            skipped.duplicates,
+            ["protokol.txt".to_string(), "protokol_kopie.txt".to_string()]
+        );
+
+        let names: Vec<String> = mail.files().iter().map(|file| file.file_name()).collect();
+        assert_eq!(names, ["protokol.txt", "faktura.txt"]);
+    }
+
+    #[test]
+    fn removed_file_keeps_values_typed_by_user() {
+        let config = test_config();
+        let mut mail = MailSender::default();
+
+        mail.add_file(vec![
+            FilePath::Path(temp_file("zakazka_100.txt", "zakázka 100")),
+            FilePath::Path(temp_file("zakazka_200.txt", "zakázka 200")),
+        ]);
+        mail.files[0].text = Some("SPZ 1AB 2345, zakázka 100".to_string());
+        mail.files[1].text = Some("zakázka 200".to_string());
+        mail.detect_vehicle_info(&config);
+
+        mail.edit_vehicle_info("vin", "TMBJJ7NE8J0123456".to_string());
+        mail.remove_file(0, &config);
+
+        let vehicle = mail.vehicle_info();
+        assert_eq!(vehicle.vin, "TMBJJ7NE8J0123456");
+        assert_eq!(vehicle.plate, "");
+        assert_eq!(vehicle.job_number, "200");
+    }
+
+    #[test]
+    fn merged_file_name_without_job_number() {
+        let mut mail = MailSender::default();
+        let date = name_template::fill_date("{datum}");
+
+        assert_eq!(mail.merged_file_name(&test_config()), format!("{date}.pdf"));
+
+        mail.vehicle.job_number = "Z-1024".to_string();
+        assert_eq!(
+            mail.merged_file_name(&test_config()),
+            format!("Z-1024_{date}.pdf")
+        );
+    }
+
+    #[test]
+    fn picked_profile_follows_removed_profile() {
+        let mut mail = MailSender::default();
+
+        mail.set_profile(Some(3));
+        mail.remove_profile(1);
+        assert_eq!(mail.profile, Some(2));
+
+        mail.remove_profile(2);
+        assert_eq!(mail.profile, None);
+    }
+
+    //answers like a submission server offering given extensions, returns everything the client sent
+    fn smtp_stand_in(
+        extensions: &'static [&'static str],
+    ) -> (u16, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut received = String::new();
+            let mut in_data = false;
+
+            let mut ehlo_reply = "250-smtp.servis.cz\r\n".to_string();
+            for (index, extension) in extensions.iter().enumerate() {
+                let separator = if index + 1 == extensions.len() {
+                    ' '
+                } else {
+                    '-'
+                };
+                ehlo_reply.push_str(&format!("250{separator}{extension}\r\n"));
+            }
+
+            stream.write_all(b"220 smtp.servis.cz\r\n").unwrap();
+
+            loop {
+                let mut line = String::new();
+
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+
+                received.push_str(&line);
+
+                let reply: &[u8] = if in_data {
+                    if line != ".\r\n" {
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 queued\r\n"
+                } else if line.starts_with("EHLO") {
+                    ehlo_reply.as_bytes()
+                } else if line.starts_with("AUTH") {
+                    b"235 ok\r\n"
+                } else if line.starts_with("DATA") {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if line.starts_with("QUIT") {
+                    stream.write_all(b"221 bye\r\n").unwrap();
+                    break;
+                } else {
+                    b"250 ok\r\n"
+                };
+
+                stream.write_all(reply).unwrap();
+            }
+
+            received
+        });
+
+        (port, server)
+    }
+
+    #[test]
+    fn delivery_status_and_read_receipt_are_requested() {
+        let config = test_config();
+        let mut mail = MailSender::default();
+
+        mail.add_person(Person {
+            name: "Jan Novák".to_string(),
+            mail: "jan@servis.cz".to_string(),
+            certificate: String::new(),
+        });
+        mail.add_file(vec![FilePath::Path(temp_file("dsn.txt", "zakázka 3"))]);
+        mail.toggle_read_receipt(&config);
+
+        let message = mail.build_message(&[], &config).unwrap();
+
+        let (port, server) = smtp_stand_in(&["AUTH PLAIN LOGIN", "8BITMIME", "DSN"]);
+        let mut connection = SmtpConnection::connect(
+            ("127.0.0.1", port),
+            Some(SMTP_TIMEOUT),
+            &ClientId::default(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        let supports_dsn = send_over_connection(
+            &mut connection,
+            &Credentials::new("dilna@servis.cz".to_string(), "heslo".to_string()),
+            &[Mechanism::Plain],
+            &message,
+        )
+        .unwrap();
+
+        let received = server.join().unwrap();
+
+        assert!(supports_dsn);
+        assert!(received.contains("MAIL FROM:<dilna@servis.cz> RET=HDRS"));
+        assert!(received.contains("RCPT TO:<jan@servis.cz> NOTIFY=SUCCESS,FAILURE"));
+        assert!(received.contains("Disposition-Notification-To: dilna@servis.cz\r\n"));
+    }
+
+    #[test]
+    fn eight_bit_body_is_not_announced_without_8bitmime() {
+        let message = Message::builder()
+            .from("dilna@servis.cz".parse().unwrap())
+            .to("jan@servis.cz".parse().unwrap())
+            .subject("Protokol")
+            .body(
+                Body::new_with_encoding("Dobrý den".to_string(), ContentTransferEncoding::EightBit)
+                    .unwrap(),
+            )
+            .unwrap();
+
+        let (port, server) = smtp_stand_in(&["AUTH PLAIN LOGIN", "DSN"]);
+        let mut connection = SmtpConnection::connect(
+            ("127.0.0.1", port),
+            Some(SMTP_TIMEOUT),
+            &ClientId::default(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        send_over_connection(
+            &mut connection,
+            &Credentials::new("dilna@servis.cz".to_string(), "heslo".to_string()),
+            &[Mechanism::Plain],
+            &message,
+        )
+        .unwrap();
+
+        let received = server.join().unwrap();
+
+        assert!(received.contains("MAIL FROM:<dilna@servis.cz> RET=HDRS\r\n"));
+        assert!(!received.contains("BODY=8BITMIME"));
+        assert!(received.contains("Dobrý den"));
+    }
+
+    #[test]
+    fn bounce_is_matched_to_built_message() {
+        let config = test_config();
+        let mut mail = MailSender::default();
+
+        mail.add_person(Person {
+            name: "Jan Novák".to_string(),
+            mail: "jan@servis.cz".to_string(),
+            certificate: String::new(),
+        });
+        mail.add_file(vec![FilePath::Path(temp_file("bounce.txt", "zakázka 4"))]);
+
+        let sent_id = message_id(&mail.build_message(&[], &config).unwrap());
+        let other_id = message_id(&mail.build_message(&[], &config).unwrap());
+
+        assert!(sent_id.starts_with('<') && sent_id.ends_with('>'));
+        assert_ne!(sent_id, other_id);
+
+        let bounce = format!(
+            "From: postmaster@servis.cz\r\nSubject: failure\r\n\r\nOriginal-Message-ID: {sent_id}\r\n"
+        );
+
+        assert_eq!(
+            mailbox_poller::referenced_id(bounce.as_bytes(), &[other_id, sent_id.clone()]),
+            Some(sent_id)
+        );
+    }
 }
@@ -0,0 +1,370 @@
+use chrono::NaiveDate;
+use mail_parser::{MessageParser, MimeHeaders};
+
+use crate::backend::config::ImapSettings;
+use crate::backend::imap_client::{ImapClient, ImapError};
+
+//---------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MailEvent {
+    Delivered,
+    Failed,
+    Read,
+}
+
+//remembers which messages were already checked, so every poll reads only new mail
+#[derive(Debug, Default)]
+pub struct MailboxPoller {
+    uid_validity: Option<u32>,
+    last_uid: u32,
+}
+
+impl MailboxPoller {
+    //returns message ids of sent mail with what happened to them
+    pub fn poll(
+        &mut self,
+        settings: &ImapSettings,
+        message_ids: &[String],
+        since: NaiveDate,
+    ) -> Result<Vec<(String, MailEvent)>, ImapError> {
+        let mut client = ImapClient::connect(settings)?;
+
+        //uids from another validity belong to different messages
+        let uid_validity = client.select(&settings.folder)?;
+        if uid_validity != self.uid_validity {
+            self.uid_validity = uid_validity;
+            self.last_uid = 0;
+        }
+
+        let mut events = vec![];
+
+        for uid in client.search_since(self.last_uid, since)? {
+            let raw_message = client.fetch(uid)?;
+            self.last_uid = self.last_uid.max(uid);
+
+            if let (Some(event), Some(message_id)) = (
+                classify(&raw_message),
+                referenced_id(&raw_message, message_ids),
+            ) {
+                events.push((message_id, event));
+            }
+        }
+
+        client.logout();
+
+        Ok(events)
+    }
+}
+
+//recognizes delivery status notifications, read receipts and plain bounces
+pub fn classify(raw_message: &[u8]) -> Option<MailEvent> {
+    let message = MessageParser::default().parse(raw_message)?;
+
+    if message.is_content_type("multipart", "report") {
+        let report_type = message
+            .content_type()
+            .and_then(|content_type| content_type.attribute("report-type"))
+            .unwrap_or_default()
+            .to_lowercase();
+
+        for part in &message.parts {
+            if report_type == "delivery-status"
+                && part.is_content_type("message", "delivery-status")
+            {
+                return delivery_status_event(part.contents());
+            }
+            if report_type == "disposition-notification"
+                && part.is_content_type("message", "disposition-notification")
+            {
+                return field_values(part.contents(), "disposition")
+                    .any(|disposition| disposition.contains("displayed"))
+                    .then_some(MailEvent::Read);
+            }
+        }
+    }
+
+    //some servers send bounces as plain text only
+    let sender = message
+        .from()
+        .and_then(|from| from.first())
+        .and_then(|from| from.address())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    (sender.starts_with("mailer-daemon@") || sender.starts_with("postmaster@"))
+        .then_some(MailEvent::Failed)
+}
+
+//report has one action per recipient, failure of anyone is reported
+fn delivery_status_event(fields: &[u8]) -> Option<MailEvent> {
+    let actions: Vec<String> = field_values(fields, "action").collect();
+
+    if actions.iter().any(|action| action == "failed") {
+        return Some(MailEvent::Failed);
+    }
+    if actions
+        .iter()
+        .any(|action| matches!(action.as_str(), "delivered" | "relayed" | "expanded"))
+    {
+        return Some(MailEvent::Delivered);
+    }
+
+    //delayed mail is still being delivered
+    None
+}
+
+fn field_values<'a>(fields: &'a [u8], name: &'a str) -> impl Iterator<Item = String> + 'a {
+    std::str::from_utf8(fields)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(move |line| {
+            let (field, value) = line.split_once(':')?;
+            field
+                .trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| value.trim().to_lowercase())
+        })
+}
+
+//report quotes original message id in headers or in Original-Message-ID field
+pub fn referenced_id(raw_message: &[u8], message_ids: &[String]) -> Option<String> {
+    let raw_message = String::from_utf8_lossy(raw_message);
+
+    message_ids
+        .iter()
+        .find(|message_id| !message_id.is_empty() && raw_message.contains(message_id.as_str()))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    const FAILED_DSN: &str = "From: Mail Delivery System <MAILER-DAEMON@mx.servis.cz>\r\n\
+To: dilna@servis.cz\r\n\
+Subject: Undelivered Mail Returned to Sender\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/report; report-type=delivery-status; boundary=\"b1\"\r\n\
+\r\n\
+--b1\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+Mail could not be delivered.\r\n\
+--b1\r\n\
+Content-Type: message/delivery-status\r\n\
+\r\n\
+Reporting-MTA: dns; mx.servis.cz\r\n\
+\r\n\
+Final-Recipient: rfc822; neexistuje@servis.cz\r\n\
+Action: failed\r\n\
+Status: 5.1.1\r\n\
+--b1\r\n\
+Content-Type: text/rfc822-headers\r\n\
+\r\n\
+From: dilna@servis.cz\r\n\
+Message-ID: <protokol-1@servis.cz>\r\n\
+--b1--\r\n";
+
+    const READ_RECEIPT: &str = "From: jan@servis.cz\r\n\
+To: dilna@servis.cz\r\n\
+Subject: Read: Protokol\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/report; report-type=disposition-notification; boundary=\"b2\"\r\n\
+\r\n\
+--b2\r\n\
+Content-Type: text/plain\r\n\
+\r\n\
+Mail was displayed.\r\n\
+--b2\r\n\
+Content-Type: message/disposition-notification\r\n\
+\r\n\
+Final-Recipient: rfc822; jan@servis.cz\r\n\
+Original-Message-ID: <protokol-2@servis.cz>\r\n\
+Disposition: manual-action/MDN-sent-manually; displayed\r\n\
+--b2--\r\n";
+
+    const DELIVERED_DSN: &str = "From: MAILER-DAEMON@mx.servis.cz\r\n\
+To: dilna@servis.cz\r\n\
+Subject: Delivery Status Notification\r\n\
+Content-Type: multipart/report; report-type=delivery-status; boundary=\"b3\"\r\n\
+\r\n\
+--b3\r\n\
+Content-Type: message/delivery-status\r\n\
+\r\n\
+Final-Recipient: rfc822; jan@servis.cz\r\n\
+Action: delivered\r\n\
+Status: 2.0.0\r\n\
+--b3--\r\n";
+
+    const DELAYED_DSN: &str = "From: MAILER-DAEMON@mx.servis.cz\r\n\
+Content-Type: multipart/report; report-type=delivery-status; boundary=\"b4\"\r\n\
+\r\n\
+--b4\r\n\
+Content-Type: message/delivery-status\r\n\
+\r\n\
+Final-Recipient: rfc822; jan@servis.cz\r\n\
+Action: delayed\r\n\
+--b4--\r\n";
+
+    const UNRELATED: &str = "From: jan@servis.cz\r\n\
+To: dilna@servis.cz\r\n\
+Subject: Re: Protokol\r\n\
+In-Reply-To: <protokol-1@servis.cz>\r\n\
+\r\n\
+Diky, protokol mame.\r\n";
+
+    //imap stand-in, answers only commands used by poller
+    fn imap_server(messages: Vec<(u32, &'static str)>) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                serve(stream.unwrap(), &messages);
+            }
+        });
+
+        port
+    }
+
+    fn serve(mut stream: TcpStream, messages: &[(u32, &str)]) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        stream.write_all(b"* OK stand-in ready\r\n").unwrap();
+
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap() == 0 {
+                return;
+            }
+
+            let (tag, command) = line.trim_end().split_once(' ').unwrap();
+            let mut reply = String::new();
+
+            if command.starts_with("LOGIN") {
+                if command != "LOGIN \"dilna@servis.cz\" \"heslo\"" {
+                    reply = format!("{tag} NO invalid credentials\r\n");
+                }
+            } else if command.starts_with("SELECT") {
+                reply.push_str(&format!("* {} EXISTS\r\n", messages.len()));
+                reply.push_str("* OK [UIDVALIDITY 7] UIDs valid\r\n");
+            } else if let Some(range) = command.strip_prefix("UID SEARCH UID ") {
+                let first: u32 = range.split(':').next().unwrap().parse().unwrap();
+                let mut uids: Vec<u32> = messages
+                    .iter()
+                    .map(|(uid, _)| *uid)
+                    .filter(|uid| *uid >= first)
+                    .collect();
+
+                //like real servers, n:* always matches the last message
+                if uids.is_empty() {
+                    uids.extend(messages.last().map(|(uid, _)| *uid));
+                }
+
+                let uids: Vec<String> = uids.iter().map(|uid| uid.to_string()).collect();
+                reply.push_str(&format!("* SEARCH {}\r\n", uids.join(" ")));
+            } else if let Some(fetch) = command.strip_prefix("UID FETCH ") {
+                let uid: u32 = fetch.split(' ').next().unwrap().parse().unwrap();
+                let (position, (_, raw_message)) = messages
+                    .iter()
+                    .enumerate()
+                    .find(|(_, (message_uid, _))| *message_uid == uid)
+                    .unwrap();
+
+                reply.push_str(&format!(
+                    "* {} FETCH (UID {uid} BODY[] {{{}}}\r\n{raw_message})\r\n",
+                    position + 1,
+                    raw_message.len()
+                ));
+            } else if command == "LOGOUT" {
+                reply.push_str("* BYE logging out\r\n");
+            }
+
+            if reply.is_empty() || !reply.starts_with(tag) {
+                reply.push_str(&format!("{tag} OK done\r\n"));
+            }
+
+            stream.write_all(reply.as_bytes()).unwrap();
+        }
+    }
+
+    fn settings(port: u16, password: &str) -> ImapSettings {
+        ImapSettings {
+            host: "127.0.0.1".to_string(),
+            port,
+            tls: false,
+            username: "dilna@servis.cz".to_string(),
+            password: password.to_string(),
+            folder: "INBOX".to_string(),
+            sent_folder: String::new(),
+        }
+    }
+
+    fn message_ids() -> Vec<String> {
+        vec![
+            "<protokol-1@servis.cz>".to_string(),
+            "<protokol-2@servis.cz>".to_string(),
+        ]
+    }
+
+    fn since() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+    }
+
+    #[test]
+    fn reports_are_linked_to_sent_mail() {
+        let port = imap_server(vec![(3, UNRELATED), (5, FAILED_DSN), (8, READ_RECEIPT)]);
+        let mut poller = MailboxPoller::default();
+
+        let events = poller
+            .poll(&settings(port, "heslo"), &message_ids(), since())
+            .unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                ("<protokol-1@servis.cz>".to_string(), MailEvent::Failed),
+                ("<protokol-2@servis.cz>".to_string(), MailEvent::Read),
+            ]
+        );
+
+        //nothing new arrived, already checked messages are skipped
+        let events = poller
+            .poll(&settings(port, "heslo"), &message_ids(), since())
+            .unwrap();
+
+        assert_eq!(events, vec![]);
+    }
+
+    #[test]
+    fn rejected_login_is_reported() {
+        let port = imap_server(vec![(1, FAILED_DSN)]);
+
+        let result =
+            MailboxPoller::default().poll(&settings(port, "spatne"), &message_ids(), since());
+
+        assert!(matches!(result, Err(ImapError::Rejected(_))));
+    }
+
+    #[test]
+    fn reports_are_classified() {
+        assert_eq!(classify(FAILED_DSN.as_bytes()), Some(MailEvent::Failed));
+        assert_eq!(classify(READ_RECEIPT.as_bytes()), Some(MailEvent::Read));
+        assert_eq!(
+            classify(DELIVERED_DSN.as_bytes()),
+            Some(MailEvent::Delivered)
+        );
+        assert_eq!(classify(DELAYED_DSN.as_bytes()), None);
+        assert_eq!(classify(UNRELATED.as_bytes()), None);
+
+        let plain_bounce = "From: postmaster@servis.cz\r\nSubject: failure\r\n\r\nMessage-ID: <protokol-1@servis.cz>\r\n";
+        assert_eq!(classify(plain_bounce.as_bytes()), Some(MailEvent::Failed));
+        assert_eq!(
+            referenced_id(plain_bounce.as_bytes(), &message_ids()),
+            Some("<protokol-1@servis.cz>".to_string())
+        );
+    }
+}
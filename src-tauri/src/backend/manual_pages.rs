@@ -0,0 +1,168 @@
+use base64::{engine::general_purpose, Engine as _};
+use pulldown_cmark::{html, CowStr, Event, Parser, Tag};
+
+use std::path::{Component, Path};
+
+use crate::backend::locale::Locale;
+
+//---------------------------
+
+const MANUAL_DIRECTORY: &str = "manuals";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ManualPage {
+    Main,
+    Settings,
+}
+
+impl ManualPage {
+    fn file_name(&self) -> &'static str {
+        match self {
+            ManualPage::Main => "manual.md",
+            ManualPage::Settings => "settings_manual.md",
+        }
+    }
+
+    fn default_text(&self, locale: Locale) -> &'static str {
+        match (self, locale) {
+            (ManualPage::Main, Locale::Cs) => include_str!("../../manuals/cs/manual.md"),
+            (ManualPage::Main, Locale::De) => include_str!("../../manuals/de/manual.md"),
+            (ManualPage::Main, Locale::Pl) => include_str!("../../manuals/pl/manual.md"),
+            (ManualPage::Main, Locale::Sk) => include_str!("../../manuals/sk/manual.md"),
+            (ManualPage::Settings, Locale::Cs) => include_str!("../../manuals/cs/settings_manual.md"),
+            (ManualPage::Settings, Locale::De) => include_str!("../../manuals/de/settings_manual.md"),
+            (ManualPage::Settings, Locale::Pl) => include_str!("../../manuals/pl/settings_manual.md"),
+            (ManualPage::Settings, Locale::Sk) => include_str!("../../manuals/sk/settings_manual.md"),
+        }
+    }
+}
+
+//page from data directory (manuals/<language>/<page>.md) wins over built-in text,
+//so wording can be fixed without rebuild
+pub fn load_page(page: ManualPage, locale: Locale) -> String {
+    let path = Path::new(MANUAL_DIRECTORY)
+        .join(locale.code())
+        .join(page.file_name());
+
+    std::fs::read_to_string(path).unwrap_or_else(|_| page.default_text(locale).to_string())
+}
+
+pub fn render_page(page: ManualPage, locale: Locale) -> String {
+    render_markdown(&load_page(page, locale), Path::new(MANUAL_DIRECTORY))
+}
+
+//relative images are read from image_directory and embedded, webview can't open local files
+pub fn render_markdown(markdown: &str, image_directory: &Path) -> String {
+    let events = Parser::new(markdown).map(|event| match event {
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => {
+            let dest_url = embedded_image(&dest_url, image_directory)
+                .map(CowStr::from)
+                .unwrap_or(dest_url);
+
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            })
+        }
+        event => event,
+    });
+
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, events);
+
+    //manual files are edited by hand, only plain formatting and embedded images are kept
+    ammonia::Builder::default()
+        .add_url_schemes(&["data"])
+        .attribute_filter(|element, attribute, value| {
+            if value.trim_start().starts_with("data:") && (element, attribute) != ("img", "src") {
+                None
+            } else {
+                Some(value.into())
+            }
+        })
+        .clean(&unsafe_html)
+        .to_string()
+}
+
+fn embedded_image(url: &str, image_directory: &Path) -> Option<String> {
+    //only plain relative paths inside image directory, no schemes, parent or root components
+    let relative = Path::new(url);
+
+    if url.contains(':')
+        || !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+
+    let path = image_directory.join(relative);
+    let mime = mime_guess::from_path(&path).first()?;
+
+    if mime.type_() != mime_guess::mime::IMAGE {
+        return None;
+    }
+
+    let image = std::fs::read(&path).ok()?;
+
+    Some(format!(
+        "data:{};base64,{}",
+        mime.essence_str(),
+        general_purpose::STANDARD.encode(image)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsafe_markup_is_removed() {
+        let html = render_markdown(
+            "1. Klikněte na <b>odeslat</b><script>alert(1)</script>\n\n[odkaz](data:text/html,ahoj) <img src=x onerror=alert(1)>",
+            Path::new(MANUAL_DIRECTORY),
+        );
+
+        assert!(html.contains("<li>Klikněte na <b>odeslat</b></li>"));
+        assert!(!html.contains("script"));
+        assert!(!html.contains("data:"));
+        assert!(!html.contains("onerror"));
+    }
+
+    #[test]
+    fn relative_image_is_embedded() {
+        let directory = std::env::temp_dir().join("mailsender_manual_images");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("pracoviste.png"), [137, 80, 78, 71]).unwrap();
+
+        let html = render_markdown(
+            "![pracoviště](pracoviste.png) ![chybí](chybi.png)",
+            &directory,
+        );
+
+        assert!(html.contains(r#"<img src="data:image/png;base64,iVBORw==" alt="pracoviště">"#));
+        assert!(html.contains(r#"<img src="chybi.png" alt="chybí">"#));
+    }
+
+    #[test]
+    fn image_outside_directory_is_not_embedded() {
+        let parent = std::env::temp_dir().join("mailsender_manual_escape");
+        let directory = parent.join("manual");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(parent.join("mimo.png"), [137, 80, 78, 71]).unwrap();
+
+        let html = render_markdown(
+            "![mimo](../mimo.png) ![mimo](manual/../../mimo.png)",
+            &directory,
+        );
+
+        assert!(!html.contains("data:"));
+    }
+}
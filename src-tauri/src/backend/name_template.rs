@@ -0,0 +1,144 @@
+use chrono::Local;
+
+use std::path::Path;
+
+use crate::backend::vehicle_info::VehicleInfo;
+
+//---------------------------
+
+//characters refused by windows and some mail clients in attachment names
+const ILLEGAL_CHARACTERS: &[char] = &['\\', '/', ':', '*', '?', '"', '<', '>', '|'];
+
+pub fn fill_date(template: &str) -> String {
+    template.replace("{datum}", &Local::now().format("%Y-%m-%d").to_string())
+}
+
+//original name is kept when template is empty, extension of original file is always kept
+pub fn attachment_file_name(
+    template: &str,
+    vehicle: &VehicleInfo,
+    sender: &str,
+    original_name: &str,
+    sequence: usize,
+) -> String {
+    if template.trim().is_empty() {
+        return original_name.to_string();
+    }
+
+    let original_path = Path::new(original_name);
+
+    let stem = original_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let file_name = fill_date(&vehicle.fill_placeholders(template))
+        .replace("{odesilatel}", sender)
+        .replace("{nazev}", &stem)
+        .replace("{poradi}", &sequence.to_string());
+
+    let file_name = match sanitize_file_name(&file_name) {
+        file_name if file_name.is_empty() => sanitize_file_name(&stem),
+        file_name => file_name,
+    };
+
+    match original_path.extension() {
+        Some(extension) => {
+            let extension = extension.to_string_lossy();
+
+            if file_name
+                .to_lowercase()
+                .ends_with(&format!(".{}", extension.to_lowercase()))
+            {
+                file_name
+            } else {
+                format!("{file_name}.{extension}")
+            }
+        }
+        None => file_name,
+    }
+}
+
+pub fn sanitize_file_name(file_name: &str) -> String {
+    let file_name: String = file_name
+        .chars()
+        .map(|character| {
+            if character.is_control() || ILLEGAL_CHARACTERS.contains(&character) {
+                '_'
+            } else {
+                character
+            }
+        })
+        .collect();
+
+    file_name.trim().trim_end_matches('.').trim_end().to_string()
+}
+
+//adds " (n)" before extension when the name was already used
+pub fn unique_file_name(file_name: String, used_names: &[String]) -> String {
+    if !used_names.contains(&file_name) {
+        return file_name;
+    }
+
+    let path = Path::new(&file_name);
+
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+
+    (1..)
+        .map(|number| format!("{stem} ({number}){extension}"))
+        .find(|file_name| !used_names.contains(file_name))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forbidden_characters_are_replaced() {
+        assert_eq!(
+            sanitize_file_name(" Zakázka 12/24: \"AB 1234\"?\t. "),
+            "Zakázka 12_24_ _AB 1234___"
+        );
+    }
+
+    #[test]
+    fn empty_result_keeps_original_name() {
+        let vehicle = VehicleInfo::default();
+
+        assert_eq!(sanitize_file_name(" . "), "");
+        assert_eq!(
+            attachment_file_name("{zakazka}", &vehicle, "Dílna", "protokol.pdf", 1),
+            "protokol.pdf"
+        );
+    }
+
+    #[test]
+    fn used_name_gets_number() {
+        let used_names = [
+            "protokol.pdf".to_string(),
+            "protokol (1).pdf".to_string(),
+            "faktura".to_string(),
+        ];
+
+        assert_eq!(
+            unique_file_name("protokol.pdf".to_string(), &used_names),
+            "protokol (2).pdf"
+        );
+        assert_eq!(
+            unique_file_name("faktura".to_string(), &used_names),
+            "faktura (1)"
+        );
+        assert_eq!(
+            unique_file_name("zapis.pdf".to_string(), &used_names),
+            "zapis.pdf"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use serde::Deserialize;
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::backend::config::OAuthSettings;
+use crate::backend::mail_sender::MailSenderError;
+
+//---------------------------
+
+//token is refreshed a bit sooner, so it can't expire during smtp login
+const EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+const DEFAULT_LIFETIME: u64 = 3600;
+
+static ACCESS_TOKENS: Mutex<Vec<CachedToken>> = Mutex::new(Vec::new());
+
+struct CachedToken {
+    key: String,
+    access_token: String,
+    expires_at: Instant,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+//access token is requested only when there is no valid one for these settings
+pub fn access_token(settings: &OAuthSettings) -> Result<String, MailSenderError> {
+    let key = format!(
+        "{}|{}|{}",
+        settings.token_endpoint, settings.client_id, settings.refresh_token
+    );
+
+    let mut tokens = ACCESS_TOKENS.lock().unwrap();
+
+    tokens.retain(|token| token.expires_at > Instant::now());
+
+    if let Some(token) = tokens.iter().find(|token| token.key == key) {
+        return Ok(token.access_token.clone());
+    }
+
+    let response = request_access_token(settings)?;
+
+    let lifetime = Duration::from_secs(response.expires_in.unwrap_or(DEFAULT_LIFETIME));
+
+    tokens.push(CachedToken {
+        key,
+        access_token: response.access_token.clone(),
+        expires_at: Instant::now() + lifetime.saturating_sub(EXPIRY_MARGIN),
+    });
+
+    Ok(response.access_token)
+}
+
+fn request_access_token(settings: &OAuthSettings) -> Result<TokenResponse, MailSenderError> {
+    if settings.token_endpoint.is_empty() || settings.refresh_token.is_empty() {
+        return Err(MailSenderError::CouldntGetAccessToken);
+    }
+
+    let mut form = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", settings.refresh_token.as_str()),
+        ("client_id", settings.client_id.as_str()),
+    ];
+
+    //public clients have no secret
+    if !settings.client_secret.is_empty() {
+        form.push(("client_secret", settings.client_secret.as_str()));
+    }
+
+    ureq::post(&settings.token_endpoint)
+        .send_form(&form)
+        .map_err(|_| MailSenderError::CouldntGetAccessToken)?
+        .into_json()
+        .map_err(|_| MailSenderError::CouldntGetAccessToken)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    //local stand-in for token endpoint, answers given responses and sends received bodies back
+    fn token_endpoint(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}/token", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+                sender.send(String::from_utf8(request_body).unwrap()).unwrap();
+
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (address, receiver)
+    }
+
+    fn settings(token_endpoint: String) -> OAuthSettings {
+        OAuthSettings {
+            client_id: "dilna".to_string(),
+            client_secret: "tajne".to_string(),
+            refresh_token: "obnovovaci".to_string(),
+            token_endpoint,
+        }
+    }
+
+    #[test]
+    fn access_token_is_requested_with_refresh_token_and_cached() {
+        let (endpoint, requests) =
+            token_endpoint(vec![(200, r#"{"access_token":"pristup","expires_in":3600}"#)]);
+        let settings = settings(endpoint);
+
+        assert_eq!(access_token(&settings).unwrap(), "pristup");
+
+        let body = requests.recv().unwrap();
+        assert!(body.contains("grant_type=refresh_token"));
+        assert!(body.contains("refresh_token=obnovovaci"));
+        assert!(body.contains("client_id=dilna"));
+        assert!(body.contains("client_secret=tajne"));
+
+        //stand-in answers only once, second token has to come from cache
+        assert_eq!(access_token(&settings).unwrap(), "pristup");
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[test]
+    fn expired_access_token_is_refreshed() {
+        let (endpoint, requests) = token_endpoint(vec![
+            (200, r#"{"access_token":"prvni","expires_in":30}"#),
+            (200, r#"{"access_token":"druhy","expires_in":3600}"#),
+        ]);
+        let settings = settings(endpoint);
+
+        //lifetime shorter than margin is treated as already expired
+        assert_eq!(access_token(&settings).unwrap(), "prvni");
+        assert_eq!(access_token(&settings).unwrap(), "druhy");
+        assert_eq!(requests.iter().take(2).count(), 2);
+    }
+
+    #[test]
+    fn rejected_refresh_token_is_error() {
+        let (endpoint, _requests) = token_endpoint(vec![(400, r#"{"error":"invalid_grant"}"#)]);
+
+        assert!(matches!(
+            access_token(&settings(endpoint)),
+            Err(MailSenderError::CouldntGetAccessToken)
+        ));
+    }
+}
@@ -1,108 +1,452 @@
+use lettre::address::AddressError;
+use lettre::message::Mailbox;
 use lettre::Address;
 use maud::{html, Markup};
 
+use crate::backend::locale::tr;
 use crate::backend::mail_list_utils::Person;
 
 //---------------------------
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtherMailRow {
+    pub id: usize,
+    pub person: Person,
+}
+
+//rows are addressed by id which is never reused, so stale html can't hit another row
 #[derive(Default, Debug)]
 pub struct OtherMailList {
-    list: Vec<Option<Person>>,
-    size: usize,
+    rows: Vec<OtherMailRow>,
+    next_id: usize,
 }
 
 impl OtherMailList {
-    pub fn size(&self) -> usize {
-        self.size
+    pub fn rows(&self) -> &[OtherMailRow] {
+        &self.rows
     }
 
     pub fn render_input_fields(&self) -> Markup {
         let markup: Markup = html! {
-            @for (index, person) in (self.list.iter().enumerate()) {
-                @if person.is_some(){
-                       div.other-mail-button-row{
-                            input.other-mail-input-field
-                            type="text"
-                            hx-post="command:edit_mail"
-                            name="text"
-                            hx-trigger="change"
-                            hx-vals={(format!(r#""index": {index}"#))}
-                            placeholder="Zadejte prosím E-mail"
-                            value=(person.as_ref().unwrap().mail)
-                            {}
-                            button.remove-button
-                            hx-post="command:remove_other_row"
-                            hx-trigger="click"
-                            hx-target="#other-mail-buttons"
-                            hx-swap="innerHTML"
-                            hx-vals={(format!(r#""index": {index}"#))}
-                            {("odstranit")}
-                       }
-                }
+            @for row in self.rows() {
+                (render_row(row.id, &row.person))
             }
             div #other-mail-list-placeholder {}
         };
         markup
     }
 
-    pub fn add_person(&mut self) {
-        self.list.push(Some(Person {
+    //adds empty row, returns its id
+    pub fn add_row(&mut self) -> usize {
+        self.add_person(Person {
             name: "".to_string(),
             mail: "".to_string(),
-        }));
+            certificate: "".to_string(),
+        })
     }
 
-    pub fn edit_person(&mut self, mail: &str, index: usize) {
-        self.list[index] = Some(Person {
-            name: mail.to_string(),
-            mail: mail.to_string(),
+    //text can contain more addresses, the rest of them is added as new rows after edited one
+    pub fn edit_row(&mut self, id: usize, text: &str) -> bool {
+        let Some(position) = self.position(id) else {
+            return false;
+        };
+
+        let mut people = parse_mailboxes(text).into_iter();
+
+        self.rows[position].person = people.next().unwrap_or(Person {
+            name: "".to_string(),
+            mail: "".to_string(),
+            certificate: "".to_string(),
         });
+
+        for (offset, person) in people.enumerate() {
+            let row = OtherMailRow {
+                id: self.take_id(),
+                person,
+            };
+
+            self.rows.insert(position + offset + 1, row);
+        }
+
+        true
     }
 
-    pub fn remove_person(&mut self, index: usize) {
-        self.list[index] = None;
+    pub fn remove_row(&mut self, id: usize) -> bool {
+        match self.position(id) {
+            Some(position) => {
+                self.rows.remove(position);
+                true
+            }
+            None => false,
+        }
     }
 
-    pub fn increment_size(&mut self) {
-        self.size += 1;
+    pub fn person(&self, id: usize) -> Option<Person> {
+        self.position(id).map(|position| self.rows[position].person.clone())
     }
 
-    pub fn remove_empty_persons(&mut self) {
-        self.list.iter_mut().for_each(|person| {
-            if person
-                .as_ref()
-                .is_some_and(|person_unwrap| person_unwrap.mail.is_empty())
-            {
-                *person = None;
+    pub fn remove_empty_rows(&mut self) {
+        self.rows.retain(|row| !row.person.mail.is_empty());
+    }
+
+    pub fn export_other_mail_list(&self) -> Vec<Person> {
+        self.rows
+            .iter()
+            .filter(|row| !row.person.mail.is_empty())
+            .map(|row| row.person.clone())
+            .collect()
+    }
+
+    //ids keep growing after clear, so rows rendered before clear stay invalid
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+
+    pub fn add_person(&mut self, person: Person) -> usize {
+        let id = self.take_id();
+
+        self.rows.push(OtherMailRow { id, person });
+
+        id
+    }
+
+    fn take_id(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn position(&self, id: usize) -> Option<usize> {
+        self.rows.iter().position(|row| row.id == id)
+    }
+}
+
+pub fn render_row(id: usize, person: &Person) -> Markup {
+    let problem = address_problem(&person.mail);
+
+    html! {
+        div.other-mail-button-row{
+            input.other-mail-input-field
+            .invalid[!person.mail.is_empty() && problem.is_some()]
+            type="text"
+            hx-post="command:edit_mail"
+            name="text"
+            hx-trigger="change"
+            hx-target="#other-mail-buttons"
+            hx-swap="innerHTML"
+            hx-vals={(format!(r#""id": {id}"#))}
+            list="other-mail-suggestions"
+            placeholder=(tr("other-placeholder"))
+            value=(display_text(person))
+            {}
+            @if person.mail.is_empty() {
+                p.other-mail-state{}
             }
-        });
+            @else if let Some(problem) = problem {
+                p.other-mail-state.invalid{(tr(problem))}
+            }
+            @else {
+                p.other-mail-state.valid{("✓")}
+            }
+            @if !person.mail.is_empty() && problem.is_none() {
+                button.remove-button
+                hx-post="command:open_promote_person"
+                hx-trigger="click"
+                hx-target="#promote-person-placeholder"
+                hx-swap="outerHTML"
+                hx-vals={(format!(r#""id": {id}"#))}
+                {(tr("other-promote"))}
+            }
+            button.remove-button
+            hx-post="command:remove_other_row"
+            hx-trigger="click"
+            hx-target="#other-mail-buttons"
+            hx-swap="innerHTML"
+            hx-vals={(format!(r#""id": {id}"#))}
+            {(tr("remove"))}
+        }
+    }
+}
+
+//message key of reason why the address can't be used, None for valid address
+pub fn address_problem(mail: &str) -> Option<&'static str> {
+    match mail.parse::<Address>() {
+        Ok(_) => None,
+        Err(AddressError::MissingParts) => Some("address-missing-parts"),
+        Err(AddressError::Unbalanced) => Some("address-unbalanced"),
+        Err(AddressError::InvalidUser) => Some("address-invalid-user"),
+        Err(AddressError::InvalidDomain) => Some("address-invalid-domain"),
+        Err(_) => Some("address-invalid-characters"),
     }
+}
+
+//"Name <mail>" is shown only when name differs from mail
+pub fn display_text(person: &Person) -> String {
+    if person.name.is_empty() || person.name == person.mail {
+        return person.mail.clone();
+    }
+
+    match person.mail.parse::<Address>() {
+        Ok(address) => Mailbox::new(Some(person.name.clone()), address).to_string(),
+        Err(_) => person.mail.clone(),
+    }
+}
 
-    pub fn export_other_mail_list(&mut self) -> Vec<Person> {
-        let mut final_vec: Vec<Person> = vec![];
+//splits pasted text on "," ";" and new lines outside of quotes and angle brackets
+pub fn parse_mailboxes(text: &str) -> Vec<Person> {
+    let mut parts: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut in_brackets = false;
 
-        self.list.iter().for_each(|person| {
-            if person.is_some() {
-                final_vec.push(person.clone().unwrap());
+    for character in text.chars() {
+        match character {
+            '"' => in_quotes = !in_quotes,
+            '<' if !in_quotes => in_brackets = true,
+            '>' if !in_quotes => in_brackets = false,
+            ',' | ';' | '\n' | '\r' if !in_quotes && !in_brackets => {
+                parts.push(std::mem::take(&mut current));
+                continue;
             }
-        });
+            _ => {}
+        }
 
-        final_vec
+        current.push(character);
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.list.is_empty()
+    parts.push(current);
+
+    parts
+        .iter()
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .flat_map(parse_mailbox)
+        .collect()
+}
+
+//addresses separated only by spaces are split as a fallback
+fn parse_mailbox(text: &str) -> Vec<Person> {
+    if let Ok(mailbox) = text.parse::<Mailbox>() {
+        let mail = mailbox.email.to_string();
+
+        return vec![Person {
+            name: mailbox.name.unwrap_or(mail.clone()),
+            mail,
+            certificate: String::new(),
+        }];
     }
 
-    pub fn is_valid(&self) -> bool {
-        self.list
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    if words.len() > 1 && words.iter().all(|word| word.parse::<Address>().is_ok()) {
+        return words
             .iter()
-            .filter_map(|person| person.as_ref())
-            .all(|person| person.mail.parse::<Address>().is_ok())
+            .map(|word| Person {
+                name: word.to_string(),
+                mail: word.to_string(),
+                certificate: String::new(),
+            })
+            .collect();
     }
 
-    pub fn clear(&mut self) {
-        self.list.clear();
-        self.size = 0;
+    vec![Person {
+        name: text.to_string(),
+        mail: text.to_string(),
+        certificate: String::new(),
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+    use regex::Regex;
+
+    #[derive(Debug, Clone)]
+    enum Operation {
+        Add,
+        Edit(usize, String),
+        Remove(usize),
+        RemoveEmpty,
+        Clear,
+    }
+
+    //row is picked from ids handed out so far, so removed and cleared ids are tried too
+    fn operation() -> impl Strategy<Value = Operation> {
+        let text = prop::sample::select(vec![
+            "",
+            "jan@servis.cz",
+            "Jan Novák <jan@servis.cz>",
+            "a@servis.cz, b@servis.cz",
+            "\"Novák, Jan\" <jan@servis.cz>; c@servis.cz",
+            "a@servis.cz b@servis.cz c@servis.cz",
+            "neplatna adresa",
+        ]);
+
+        prop_oneof![
+            Just(Operation::Add),
+            (any::<usize>(), text).prop_map(|(pick, text)| Operation::Edit(pick, text.to_string())),
+            any::<usize>().prop_map(Operation::Remove),
+            Just(Operation::RemoveEmpty),
+            Just(Operation::Clear),
+        ]
+    }
+
+    fn empty_person() -> Person {
+        Person {
+            name: "".to_string(),
+            mail: "".to_string(),
+            certificate: "".to_string(),
+        }
+    }
+
+    fn rendered_ids(list: &OtherMailList) -> Vec<usize> {
+        let id_regex = Regex::new(r"&quot;id&quot;: (\d+)").unwrap();
+        let html = list.render_input_fields().into_string();
+
+        let mut ids: Vec<usize> = id_regex
+            .captures_iter(&html)
+            .map(|captures| captures[1].parse().unwrap())
+            .collect();
+
+        ids.dedup();
+        ids
+    }
+
+    proptest! {
+        #[test]
+        fn rows_stay_in_sync(operations in prop::collection::vec(operation(), 0..40)) {
+            let mut list = OtherMailList::default();
+            let mut model: Vec<(usize, Person)> = vec![];
+            let mut next_id = 0;
+
+            for operation in operations {
+                match operation {
+                    Operation::Add => {
+                        let id = list.add_row();
+
+                        prop_assert_eq!(id, next_id);
+                        model.push((id, empty_person()));
+                        next_id += 1;
+                    }
+                    Operation::Edit(pick, text) => {
+                        let id = pick % (next_id + 1);
+                        let edited = list.edit_row(id, &text);
+
+                        match model.iter().position(|(row_id, _)| *row_id == id) {
+                            Some(position) => {
+                                prop_assert!(edited);
+
+                                let mut people = parse_mailboxes(&text).into_iter();
+                                model[position].1 = people.next().unwrap_or(empty_person());
+
+                                for (offset, person) in people.enumerate() {
+                                    model.insert(position + offset + 1, (next_id, person));
+                                    next_id += 1;
+                                }
+                            }
+                            None => prop_assert!(!edited),
+                        }
+                    }
+                    Operation::Remove(pick) => {
+                        let id = pick % (next_id + 1);
+                        let removed = list.remove_row(id);
+
+                        let position = model.iter().position(|(row_id, _)| *row_id == id);
+                        prop_assert_eq!(removed, position.is_some());
+
+                        if let Some(position) = position {
+                            model.remove(position);
+                        }
+                    }
+                    Operation::RemoveEmpty => {
+                        list.remove_empty_rows();
+                        model.retain(|(_, person)| !person.mail.is_empty());
+                    }
+                    Operation::Clear => {
+                        list.clear();
+                        model.clear();
+                    }
+                }
+
+                let rows: Vec<(usize, Person)> = list
+                    .rows()
+                    .iter()
+                    .map(|row| (row.id, row.person.clone()))
+                    .collect();
+
+                prop_assert_eq!(&rows, &model);
+
+                let model_ids: Vec<usize> = model.iter().map(|(id, _)| *id).collect();
+                prop_assert_eq!(rendered_ids(&list), model_ids);
+
+                for (id, person) in &model {
+                    prop_assert_eq!(list.person(*id), Some(person.clone()));
+                }
+
+                let exported: Vec<Person> = model
+                    .iter()
+                    .map(|(_, person)| person.clone())
+                    .filter(|person| !person.mail.is_empty())
+                    .collect();
+                prop_assert_eq!(list.export_other_mail_list(), exported);
+            }
+        }
+
+        #[test]
+        fn ids_are_never_reused(adds in 1..20usize, clears in 0..5usize) {
+            let mut list = OtherMailList::default();
+            let mut seen: Vec<usize> = vec![];
+
+            for _ in 0..=clears {
+                for _ in 0..adds {
+                    let id = list.add_row();
+
+                    prop_assert!(!seen.contains(&id));
+                    seen.push(id);
+                }
+
+                let stale = seen[0];
+                list.clear();
+
+                prop_assert!(!list.edit_row(stale, "jan@servis.cz"));
+                prop_assert!(list.person(stale).is_none());
+            }
+        }
+    }
+
+    fn names_and_mails(text: &str) -> Vec<(String, String)> {
+        parse_mailboxes(text)
+            .into_iter()
+            .map(|person| (person.name, person.mail))
+            .collect()
+    }
+
+    #[test]
+    fn quoted_name_with_comma_stays_together() {
+        assert_eq!(
+            names_and_mails(r#""Novák, Jan" <jan@servis.cz>, Petr <petr@servis.cz>"#),
+            [
+                ("Novák, Jan".to_string(), "jan@servis.cz".to_string()),
+                ("Petr".to_string(), "petr@servis.cz".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn semicolons_and_new_lines_separate_addresses() {
+        let mails: Vec<String> = parse_mailboxes("a@servis.cz; b@servis.cz\r\nc@servis.cz;")
+            .into_iter()
+            .map(|person| person.mail)
+            .collect();
+
+        assert_eq!(mails, ["a@servis.cz", "b@servis.cz", "c@servis.cz"]);
+    }
+
+    #[test]
+    fn empty_input_gives_no_address() {
+        assert!(parse_mailboxes("").is_empty());
+        assert!(parse_mailboxes(" ,; \n ").is_empty());
     }
 }
@@ -0,0 +1,90 @@
+use base64::prelude::*;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use std::sync::Mutex;
+
+use crate::backend::config::ImapSettings;
+use crate::backend::imap_client::{ImapClient, ImapError};
+
+//---------------------------
+
+//copy of sent mail waiting to be stored in sent folder
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SentCopy {
+    pub time: DateTime<Local>,
+    pub message_id: String,
+    message: String,
+}
+
+//copies are kept on disk until imap server accepts them, so none is lost on restart
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Outbox {
+    copies: Vec<SentCopy>,
+}
+
+impl Outbox {
+    pub fn load_outbox() -> Outbox {
+        match std::fs::read_to_string("outbox.ron") {
+            Ok(ron_string) => ron::de::from_str(&ron_string).unwrap_or_else(|error| {
+                eprintln!("couldn't read outbox.ron, starting with empty outbox: {error}");
+                Outbox::default()
+            }),
+            Err(_) => Outbox::default(),
+        }
+    }
+
+    pub fn save_outbox(&self) {
+        let ron_string =
+            ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).unwrap();
+
+        std::fs::write("outbox.ron", ron_string).unwrap();
+    }
+
+    //message is stored exactly as it was sent, base64 keeps it intact in ron
+    pub fn add_copy(&mut self, message_id: &str, message: &[u8]) {
+        self.copies.push(SentCopy {
+            time: Local::now(),
+            message_id: message_id.to_string(),
+            message: BASE64_STANDARD.encode(message),
+        });
+        self.save_outbox();
+    }
+
+    //stored copies are removed, the rest waits for next attempt (folder can be fixed meanwhile)
+    //outbox isn't locked while server is contacted, only one flush runs at a time
+    pub fn flush(outbox: &Mutex<Outbox>, settings: &ImapSettings) -> Result<(), ImapError> {
+        static FLUSHING: Mutex<()> = Mutex::new(());
+        let _flushing = FLUSHING.lock().unwrap();
+
+        let copies = outbox.lock().unwrap().copies.clone();
+
+        if copies.is_empty() {
+            return Ok(());
+        }
+
+        let mut client = ImapClient::connect(settings)?;
+        let mut stored = 0;
+        let mut result = Ok(());
+
+        for copy in &copies {
+            let message = BASE64_STANDARD.decode(&copy.message).unwrap_or_default();
+
+            if let Err(error) = client.append(settings.sent_folder.trim(), &message) {
+                result = Err(error);
+                break;
+            }
+
+            stored += 1;
+        }
+
+        client.logout();
+
+        //copies added meanwhile are behind the flushed ones
+        let mut outbox = outbox.lock().unwrap();
+        outbox.copies.drain(..stored);
+        outbox.save_outbox();
+
+        result
+    }
+}
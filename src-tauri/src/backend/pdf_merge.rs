@@ -0,0 +1,307 @@
+use lopdf::{Document, Object, ObjectId};
+
+use std::collections::BTreeMap;
+
+use crate::backend::mail_sender::MailSenderError;
+
+//---------------------------
+
+//page attributes a page may take over from its pages nodes
+const INHERITABLE_ATTRIBUTES: &[&[u8]] = &[b"Resources", b"MediaBox", b"CropBox", b"Rotate"];
+
+//concatenates pages of all documents in given order
+pub fn merge_pdfs(files: &[Vec<u8>]) -> Result<Vec<u8>, MailSenderError> {
+    let mut max_id = 1;
+    let mut root_ids: Option<(ObjectId, ObjectId)> = None;
+    let mut pages: Vec<(ObjectId, Object)> = vec![];
+    let mut objects: BTreeMap<ObjectId, Object> = BTreeMap::new();
+
+    for content in files {
+        let mut document =
+            Document::load_mem(content).map_err(|_| MailSenderError::CouldntMergePdf)?;
+
+        document.renumber_objects_with(max_id);
+        max_id = document.max_id + 1;
+
+        if root_ids.is_none() {
+            root_ids = Some(root_ids_of(&document).ok_or(MailSenderError::CouldntMergePdf)?);
+        }
+
+        for page_id in document.get_pages().into_values() {
+            let page = document
+                .get_object(page_id)
+                .map_err(|_| MailSenderError::CouldntMergePdf)?
+                .to_owned();
+
+            pages.push((page_id, with_inherited_attributes(&document, page)));
+        }
+
+        objects.extend(document.objects);
+    }
+
+    let mut merged = Document::with_version("1.5");
+
+    //catalog and pages root of first document are reused, pages are added separately
+    let (catalog_id, pages_root_id) = root_ids.ok_or(MailSenderError::CouldntMergePdf)?;
+    let catalog = objects
+        .remove(&catalog_id)
+        .ok_or(MailSenderError::CouldntMergePdf)?;
+    let pages_root = objects
+        .remove(&pages_root_id)
+        .ok_or(MailSenderError::CouldntMergePdf)?;
+
+    for (object_id, object) in objects {
+        match object.type_name().unwrap_or(b"") {
+            b"Catalog" | b"Pages" | b"Page" | b"Outlines" | b"Outline" => {}
+            _ => {
+                merged.objects.insert(object_id, object);
+            }
+        }
+    }
+
+    let mut kids: Vec<Object> = vec![];
+
+    for (page_id, page) in pages {
+        if let Ok(dictionary) = page.as_dict() {
+            let mut dictionary = dictionary.clone();
+            dictionary.set("Parent", pages_root_id);
+
+            merged
+                .objects
+                .insert(page_id, Object::Dictionary(dictionary));
+            kids.push(Object::Reference(page_id));
+        }
+    }
+
+    let mut pages_dictionary = pages_root
+        .as_dict()
+        .map_err(|_| MailSenderError::CouldntMergePdf)?
+        .clone();
+
+    //pages already carry inherited attributes, root mustn't pass its own to other documents
+    pages_dictionary.remove(b"Parent");
+    for attribute in INHERITABLE_ATTRIBUTES {
+        pages_dictionary.remove(attribute);
+    }
+
+    pages_dictionary.set("Count", kids.len() as u32);
+    pages_dictionary.set("Kids", kids);
+
+    merged
+        .objects
+        .insert(pages_root_id, Object::Dictionary(pages_dictionary));
+
+    let mut catalog_dictionary = catalog
+        .as_dict()
+        .map_err(|_| MailSenderError::CouldntMergePdf)?
+        .clone();
+
+    catalog_dictionary.set("Pages", pages_root_id);
+    catalog_dictionary.remove(b"Outlines");
+
+    merged
+        .objects
+        .insert(catalog_id, Object::Dictionary(catalog_dictionary));
+
+    merged.trailer.set("Root", catalog_id);
+    merged.max_id = merged.objects.keys().map(|(id, _)| *id).max().unwrap_or(0);
+    merged.renumber_objects();
+
+    let mut result: Vec<u8> = vec![];
+
+    merged
+        .save_to(&mut result)
+        .map_err(|_| MailSenderError::CouldntMergePdf)?;
+
+    Ok(result)
+}
+
+//catalog from trailer and pages root from catalog, first pages object may be an inner node
+fn root_ids_of(document: &Document) -> Option<(ObjectId, ObjectId)> {
+    let catalog_id = document.trailer.get(b"Root").ok()?.as_reference().ok()?;
+    let pages_root_id = document
+        .get_dictionary(catalog_id)
+        .ok()?
+        .get(b"Pages")
+        .ok()?
+        .as_reference()
+        .ok()?;
+
+    Some((catalog_id, pages_root_id))
+}
+
+//pages are re-parented to one pages root, so attributes of the old parents have to be copied
+fn with_inherited_attributes(document: &Document, page: Object) -> Object {
+    let Ok(mut dictionary) = page.as_dict().cloned() else {
+        return page;
+    };
+
+    let mut parent_id = dictionary
+        .get(b"Parent")
+        .and_then(Object::as_reference)
+        .ok();
+    let mut visited: Vec<ObjectId> = vec![];
+
+    //broken files may have parent cycles
+    while let Some(id) = parent_id.filter(|id| !visited.contains(id)) {
+        visited.push(id);
+
+        let Ok(parent) = document.get_dictionary(id) else {
+            break;
+        };
+
+        for attribute in INHERITABLE_ATTRIBUTES {
+            if !dictionary.has(attribute) {
+                if let Ok(value) = parent.get(attribute) {
+                    dictionary.set(*attribute, value.clone());
+                }
+            }
+        }
+
+        parent_id = parent.get(b"Parent").and_then(Object::as_reference).ok();
+    }
+
+    Object::Dictionary(dictionary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lopdf::dictionary;
+
+    //pages take media box from the pages root, or from a nested pages node
+    fn document(media_box: [i64; 4], page_count: usize, nested: bool) -> Vec<u8> {
+        let mut document = Document::with_version("1.5");
+
+        //inner node gets lower id, so it comes before the root in object order
+        let kids_parent_id = document.new_object_id();
+        let pages_id = if nested {
+            document.new_object_id()
+        } else {
+            kids_parent_id
+        };
+
+        let kids: Vec<Object> = (0..page_count)
+            .map(|_| {
+                document
+                    .add_object(dictionary! {
+                        "Type" => "Page",
+                        "Parent" => kids_parent_id,
+                    })
+                    .into()
+            })
+            .collect();
+
+        let media_box: Vec<Object> = media_box.into_iter().map(Object::from).collect();
+
+        if nested {
+            document.objects.insert(
+                kids_parent_id,
+                Object::Dictionary(dictionary! {
+                    "Type" => "Pages",
+                    "Parent" => pages_id,
+                    "Kids" => kids,
+                    "Count" => page_count as u32,
+                    "MediaBox" => media_box,
+                }),
+            );
+            document.objects.insert(
+                pages_id,
+                Object::Dictionary(dictionary! {
+                    "Type" => "Pages",
+                    "Kids" => vec![Object::Reference(kids_parent_id)],
+                    "Count" => page_count as u32,
+                }),
+            );
+        } else {
+            document.objects.insert(
+                pages_id,
+                Object::Dictionary(dictionary! {
+                    "Type" => "Pages",
+                    "Kids" => kids,
+                    "Count" => page_count as u32,
+                    "MediaBox" => media_box,
+                }),
+            );
+        }
+
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        document.trailer.set("Root", catalog_id);
+
+        let mut content: Vec<u8> = vec![];
+        document.save_to(&mut content).unwrap();
+
+        content
+    }
+
+    #[test]
+    fn inherited_media_box_is_kept() {
+        let merged = merge_pdfs(&[
+            document([0, 0, 595, 842], 2, false),
+            document([0, 0, 842, 595], 1, true),
+        ])
+        .unwrap();
+
+        let merged = Document::load_mem(&merged).unwrap();
+
+        let media_boxes: Vec<Vec<i64>> = merged
+            .get_pages()
+            .into_values()
+            .map(|page_id| {
+                merged
+                    .get_dictionary(page_id)
+                    .unwrap()
+                    .get(b"MediaBox")
+                    .unwrap()
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|value| value.as_i64().unwrap())
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(
+            media_boxes,
+            [[0, 0, 595, 842], [0, 0, 595, 842], [0, 0, 842, 595]]
+        );
+    }
+
+    #[test]
+    fn nested_page_tree_root_is_used() {
+        let merged = merge_pdfs(&[
+            document([0, 0, 842, 595], 2, true),
+            document([0, 0, 595, 842], 1, false),
+        ])
+        .unwrap();
+
+        let merged = Document::load_mem(&merged).unwrap();
+
+        let pages_root_id = merged
+            .catalog()
+            .unwrap()
+            .get(b"Pages")
+            .unwrap()
+            .as_reference()
+            .unwrap();
+        let pages_root = merged.get_dictionary(pages_root_id).unwrap();
+
+        assert!(!pages_root.has(b"Parent"));
+        assert!(!pages_root.has(b"MediaBox"));
+        assert_eq!(pages_root.get(b"Count").unwrap().as_i64().unwrap(), 3);
+
+        for page_id in merged.get_pages().into_values() {
+            let page = merged.get_dictionary(page_id).unwrap();
+
+            assert_eq!(
+                page.get(b"Parent").unwrap().as_reference().unwrap(),
+                pages_root_id
+            );
+            assert!(page.has(b"MediaBox"));
+        }
+    }
+}
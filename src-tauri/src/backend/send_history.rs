@@ -0,0 +1,126 @@
+use chrono::{DateTime, Duration, Local};
+use serde::{Deserialize, Serialize};
+
+use std::path::PathBuf;
+
+use crate::backend::mail_list_utils::Person;
+use crate::backend::mailbox_poller::MailEvent;
+use crate::backend::vehicle_info::VehicleInfo;
+
+//---------------------------
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SentFile {
+    pub name: String,
+    pub final_path: PathBuf,
+    //file stays at final_path when move or rename failed
+    #[serde(default)]
+    pub post_send_error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HistoryEntry {
+    pub time: DateTime<Local>,
+    pub recipients: Vec<String>,
+    pub files: Vec<SentFile>,
+    #[serde(default)]
+    pub vehicle: VehicleInfo,
+    #[serde(default)]
+    pub roster_recipients: Vec<Person>,
+    #[serde(default)]
+    pub other_recipients: Vec<Person>,
+    #[serde(default)]
+    pub author: Option<Person>,
+    #[serde(default)]
+    pub sender: String,
+    //delivery status is what was requested, server may not support it
+    #[serde(default)]
+    pub delivery_status: bool,
+    #[serde(default)]
+    pub delivery_status_unsupported: bool,
+    #[serde(default)]
+    pub read_receipt: bool,
+    #[serde(default)]
+    pub message_id: String,
+    #[serde(default)]
+    pub status: MailStatus,
+}
+
+//filled in from bounces and receipts found in sender mailbox
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub enum MailStatus {
+    #[default]
+    Sent,
+    Delivered,
+    Read,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SendHistory {
+    entries: Vec<HistoryEntry>,
+}
+
+impl SendHistory {
+    pub fn load_history() -> SendHistory {
+        match std::fs::read_to_string("send_history.ron") {
+            Ok(ron_string) => ron::de::from_str(&ron_string).unwrap_or_default(),
+            Err(_) => SendHistory::default(),
+        }
+    }
+
+    pub fn save_history(&self) {
+        let ron_string =
+            ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).unwrap();
+
+        std::fs::write("send_history.ron", ron_string).unwrap();
+    }
+
+    pub fn add_entry(&mut self, entry: HistoryEntry) {
+        self.entries.push(entry);
+        self.save_history();
+    }
+
+    pub fn last_entry(&self) -> Option<&HistoryEntry> {
+        self.entries.last()
+    }
+
+    //answers for older mail are not expected anymore
+    pub fn pending_message_ids(&self, days: i64) -> Vec<String> {
+        let since = Local::now() - Duration::days(days);
+
+        self.entries
+            .iter()
+            .filter(|entry| entry.time > since && !entry.message_id.is_empty())
+            .filter(|entry| match entry.status {
+                MailStatus::Sent => true,
+                MailStatus::Delivered => entry.read_receipt,
+                MailStatus::Read | MailStatus::Failed => false,
+            })
+            .map(|entry| entry.message_id.clone())
+            .collect()
+    }
+
+    //failure is final, otherwise status only moves forward (receipt can come before dsn)
+    pub fn apply_event(&mut self, message_id: &str, event: MailEvent) -> Option<&HistoryEntry> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.message_id == message_id)?;
+
+        let status = match event {
+            MailEvent::Delivered => MailStatus::Delivered,
+            MailEvent::Read => MailStatus::Read,
+            MailEvent::Failed => MailStatus::Failed,
+        };
+
+        if entry.status == MailStatus::Failed || entry.status >= status {
+            return None;
+        }
+
+        entry.status = status;
+        self.save_history();
+
+        self.entries.iter().find(|entry| entry.message_id == message_id)
+    }
+}
@@ -0,0 +1,322 @@
+use lettre::message::header::{ContentDisposition, ContentType};
+use lettre::message::{Body, MessageBuilder, MultiPart, SinglePart};
+use lettre::Message;
+use openssl::pkcs12::Pkcs12;
+use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+use openssl::pkey::{PKey, Private};
+use openssl::stack::Stack;
+use openssl::symm::Cipher;
+use openssl::x509::X509;
+
+use crate::backend::config::SmimeSettings;
+use crate::backend::mail_sender::{MailSenderError, Recipient};
+
+//---------------------------
+
+struct SenderIdentity {
+    certificate: X509,
+    key: PKey<Private>,
+    chain: Stack<X509>,
+}
+
+//message key of reason why configured s/mime can't be used, None when it is turned off or valid
+pub fn smime_problem(settings: &SmimeSettings) -> Option<&'static str> {
+    if settings.certificate_path.trim().is_empty() {
+        if settings.encrypt {
+            return Some("smime-encrypt-needs-certificate");
+        }
+        return None;
+    }
+
+    match std::fs::read(&settings.certificate_path) {
+        Ok(der) => match sender_identity(&der, &settings.certificate_password) {
+            Ok(_) => None,
+            Err(_) => Some("smime-invalid-certificate"),
+        },
+        Err(_) => Some("smime-unreadable-certificate"),
+    }
+}
+
+//message key of reason why mail can't be encrypted to this recipient
+pub fn recipient_problem(certificate_path: &str) -> Option<&'static str> {
+    if certificate_path.trim().is_empty() {
+        return Some("recipient-missing-certificate");
+    }
+
+    match recipient_certificate(certificate_path) {
+        Ok(_) => None,
+        Err(_) => Some("recipient-unreadable-certificate"),
+    }
+}
+
+//content is signed first and then encrypted, so only recipients can see the signature
+pub fn build_message(
+    message_builder: MessageBuilder,
+    content: MultiPart,
+    recipients: &[Recipient],
+    settings: &SmimeSettings,
+) -> Result<Message, MailSenderError> {
+    if settings.certificate_path.trim().is_empty() {
+        return message_builder
+            .multipart(content)
+            .map_err(|_| MailSenderError::InvalidMessage);
+    }
+
+    let der = std::fs::read(&settings.certificate_path)
+        .map_err(|_| MailSenderError::CouldntProtectMessage)?;
+    let sender = sender_identity(&der, &settings.certificate_password)?;
+
+    let signed = sign(content, &sender)?;
+
+    if !settings.encrypt {
+        return message_builder
+            .multipart(signed)
+            .map_err(|_| MailSenderError::InvalidMessage);
+    }
+
+    message_builder
+        .singlepart(encrypt(&signed.formatted(), recipients, &sender)?)
+        .map_err(|_| MailSenderError::InvalidMessage)
+}
+
+fn sign(content: MultiPart, sender: &SenderIdentity) -> Result<MultiPart, MailSenderError> {
+    //line break before next boundary belongs to the boundary, it is not signed
+    let formatted = content.formatted();
+    let signed_content = formatted.strip_suffix(b"\r\n").unwrap_or(&formatted);
+
+    let signature = Pkcs7::sign(
+        &sender.certificate,
+        &sender.key,
+        &sender.chain,
+        signed_content,
+        Pkcs7Flags::DETACHED | Pkcs7Flags::BINARY,
+    )
+    .and_then(|signature| signature.to_der())
+    .map_err(|_| MailSenderError::CouldntProtectMessage)?;
+
+    Ok(MultiPart::signed(
+        "application/pkcs7-signature".to_string(),
+        "sha-256".to_string(),
+    )
+    .multipart(content)
+    .singlepart(smime_part(
+        "application/pkcs7-signature; name=\"smime.p7s\"",
+        "smime.p7s",
+        signature,
+    )?))
+}
+
+//sender is one of recipients too, so copy of sent mail stays readable
+fn encrypt(
+    content: &[u8],
+    recipients: &[Recipient],
+    sender: &SenderIdentity,
+) -> Result<SinglePart, MailSenderError> {
+    let mut certificates = Stack::new().map_err(|_| MailSenderError::CouldntProtectMessage)?;
+
+    for recipient in recipients {
+        let certificate = recipient_certificate(&recipient.certificate).map_err(|_| {
+            MailSenderError::MissingRecipientCertificate(recipient.mail.to_string())
+        })?;
+
+        certificates
+            .push(certificate)
+            .map_err(|_| MailSenderError::CouldntProtectMessage)?;
+    }
+
+    certificates
+        .push(sender.certificate.clone())
+        .map_err(|_| MailSenderError::CouldntProtectMessage)?;
+
+    let encrypted = Pkcs7::encrypt(
+        &certificates,
+        content,
+        Cipher::aes_256_cbc(),
+        Pkcs7Flags::BINARY,
+    )
+    .and_then(|encrypted| encrypted.to_der())
+    .map_err(|_| MailSenderError::CouldntProtectMessage)?;
+
+    smime_part(
+        "application/pkcs7-mime; smime-type=enveloped-data; name=\"smime.p7m\"",
+        "smime.p7m",
+        encrypted,
+    )
+}
+
+fn smime_part(
+    content_type: &str,
+    file_name: &str,
+    der: Vec<u8>,
+) -> Result<SinglePart, MailSenderError> {
+    Ok(SinglePart::builder()
+        .header(ContentType::parse(content_type).map_err(|_| MailSenderError::InvalidMessage)?)
+        .header(ContentDisposition::attachment(file_name))
+        .body(Body::new(der)))
+}
+
+fn sender_identity(der: &[u8], password: &str) -> Result<SenderIdentity, MailSenderError> {
+    let parsed = Pkcs12::from_der(der)
+        .and_then(|pkcs12| pkcs12.parse2(password))
+        .map_err(|_| MailSenderError::CouldntProtectMessage)?;
+
+    let (Some(certificate), Some(key)) = (parsed.cert, parsed.pkey) else {
+        return Err(MailSenderError::CouldntProtectMessage);
+    };
+
+    let chain = match parsed.ca {
+        Some(chain) => chain,
+        None => Stack::new().map_err(|_| MailSenderError::CouldntProtectMessage)?,
+    };
+
+    Ok(SenderIdentity {
+        certificate,
+        key,
+        chain,
+    })
+}
+
+//certificates are exported as pem or der, both are accepted
+fn recipient_certificate(path: &str) -> Result<X509, MailSenderError> {
+    let certificate = std::fs::read(path).map_err(|_| MailSenderError::CouldntProtectMessage)?;
+
+    X509::from_pem(&certificate)
+        .or_else(|_| X509::from_der(&certificate))
+        .map_err(|_| MailSenderError::CouldntProtectMessage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use openssl::asn1::Asn1Time;
+    use openssl::bn::BigNum;
+    use openssl::hash::MessageDigest;
+    use openssl::rsa::Rsa;
+    use openssl::x509::store::X509StoreBuilder;
+    use openssl::x509::X509NameBuilder;
+
+    use std::path::PathBuf;
+
+    //throwaway self-signed certificate, good enough for signing and encryption
+    fn identity(mail: &str) -> (X509, PKey<Private>) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", mail).unwrap();
+        let name = name.build();
+
+        let mut certificate = X509::builder().unwrap();
+        certificate.set_version(2).unwrap();
+        certificate
+            .set_serial_number(&BigNum::from_u32(1).unwrap().to_asn1_integer().unwrap())
+            .unwrap();
+        certificate.set_subject_name(&name).unwrap();
+        certificate.set_issuer_name(&name).unwrap();
+        certificate.set_pubkey(&key).unwrap();
+        certificate
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        certificate
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        certificate.sign(&key, MessageDigest::sha256()).unwrap();
+
+        (certificate.build(), key)
+    }
+
+    fn temp_file(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("mailsender_smime_{name}"));
+        std::fs::write(&path, content).unwrap();
+
+        path
+    }
+
+    fn settings(
+        name: &str,
+        certificate: &X509,
+        key: &PKey<Private>,
+        encrypt: bool,
+    ) -> SmimeSettings {
+        let pkcs12 = Pkcs12::builder()
+            .name("dilna")
+            .pkey(key)
+            .cert(certificate)
+            .build2("heslo")
+            .unwrap();
+
+        SmimeSettings {
+            certificate_path: temp_file(name, &pkcs12.to_der().unwrap())
+                .to_string_lossy()
+                .to_string(),
+            certificate_password: "heslo".to_string(),
+            encrypt,
+        }
+    }
+
+    fn message(recipients: &[Recipient], settings: &SmimeSettings) -> Vec<u8> {
+        let message_builder = Message::builder()
+            .from("Dílna <dilna@servis.cz>".parse().unwrap())
+            .to("jan@servis.cz".parse().unwrap())
+            .subject("Protokol");
+
+        let content =
+            MultiPart::mixed().singlepart(SinglePart::plain("protokol je v priloze".to_string()));
+
+        build_message(message_builder, content, recipients, settings)
+            .unwrap()
+            .formatted()
+    }
+
+    #[test]
+    fn signed_message_verifies() {
+        let (certificate, key) = identity("dilna@servis.cz");
+        let settings = settings("signed.p12", &certificate, &key, false);
+
+        let (signature, content) = Pkcs7::from_smime(&message(&[], &settings)).unwrap();
+        let content = content.unwrap();
+
+        let mut store = X509StoreBuilder::new().unwrap();
+        store.add_cert(certificate).unwrap();
+
+        let mut output = vec![];
+        signature
+            .verify(
+                &Stack::new().unwrap(),
+                &store.build(),
+                Some(&content),
+                Some(&mut output),
+                Pkcs7Flags::BINARY,
+            )
+            .unwrap();
+
+        assert!(String::from_utf8_lossy(&output).contains("protokol je v priloze"));
+    }
+
+    #[test]
+    fn encrypted_message_is_readable_by_recipient() {
+        let (sender_certificate, sender_key) = identity("dilna@servis.cz");
+        let (recipient_certificate, recipient_key) = identity("jan@servis.cz");
+        let settings = settings("encrypted.p12", &sender_certificate, &sender_key, true);
+
+        let recipient = Recipient {
+            name: "Jan Novák".to_string(),
+            mail: "jan@servis.cz".parse().unwrap(),
+            certificate: temp_file("jan.pem", &recipient_certificate.to_pem().unwrap())
+                .to_string_lossy()
+                .to_string(),
+        };
+
+        let message = message(&[recipient], &settings);
+        assert!(!String::from_utf8_lossy(&message).contains("protokol je v priloze"));
+
+        let (encrypted, _) = Pkcs7::from_smime(&message).unwrap();
+        let decrypted = encrypted
+            .decrypt(&recipient_key, &recipient_certificate, Pkcs7Flags::empty())
+            .unwrap();
+        let decrypted = String::from_utf8_lossy(&decrypted);
+
+        assert!(decrypted.contains("multipart/signed"));
+        assert!(decrypted.contains("protokol je v priloze"));
+    }
+}
@@ -0,0 +1,78 @@
+use base64::prelude::*;
+use image::ImageFormat;
+use pdfium_render::prelude::*;
+
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::OnceLock;
+
+//---------------------------
+
+const THUMBNAIL_DIRECTORY: &str = "thumbnails";
+const THUMBNAIL_WIDTH: i32 = 300;
+
+//first page of pdf as png data url, rendered images are cached by file hash
+pub fn load_thumbnail(content: &[u8], hash: &str) -> Option<String> {
+    let cache_path = Path::new(THUMBNAIL_DIRECTORY).join(format!("{hash}.png"));
+
+    let png = match fs::read(&cache_path) {
+        Ok(png) => png,
+        Err(_) => {
+            let png = render_first_page(content)?;
+
+            if fs::create_dir_all(THUMBNAIL_DIRECTORY).is_ok() {
+                fs::write(&cache_path, &png).ok();
+            }
+
+            png
+        }
+    };
+
+    Some(format!(
+        "data:image/png;base64,{}",
+        BASE64_STANDARD.encode(png)
+    ))
+}
+
+//pdfium isn't bundled, without it files are listed without thumbnails
+pub fn renderer_available() -> bool {
+    pdfium().is_some()
+}
+
+//library is bound once and shared by all renders
+fn pdfium() -> Option<&'static Pdfium> {
+    static PDFIUM: OnceLock<Option<Pdfium>> = OnceLock::new();
+
+    PDFIUM
+        .get_or_init(|| bind_pdfium().map(Pdfium::new))
+        .as_ref()
+}
+
+//pdfium library (pdfium.dll, libpdfium.so) in the working directory is preferred over system one
+fn bind_pdfium() -> Option<Box<dyn PdfiumLibraryBindings>> {
+    Pdfium::bind_to_library(Pdfium::pdfium_platform_library_name_at_path("./"))
+        .or_else(|_| Pdfium::bind_to_system_library())
+        .ok()
+}
+
+//rendering runs on cpu
+fn render_first_page(content: &[u8]) -> Option<Vec<u8>> {
+    let pdfium = pdfium()?;
+
+    let document = pdfium.load_pdf_from_byte_slice(content, None).ok()?;
+
+    let image = document
+        .pages()
+        .first()
+        .ok()?
+        .render_with_config(&PdfRenderConfig::new().set_target_width(THUMBNAIL_WIDTH))
+        .ok()?
+        .as_image();
+
+    let mut png = Cursor::new(vec![]);
+
+    image.write_to(&mut png, ImageFormat::Png).ok()?;
+
+    Some(png.into_inner())
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+
+//---------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tour {
+    Main,
+    Settings,
+}
+
+impl Tour {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Tour::Main => "main",
+            Tour::Settings => "settings",
+        }
+    }
+
+    //unknown code falls back to main screen tour
+    pub fn from_code(code: &str) -> Tour {
+        match code {
+            "settings" => Tour::Settings,
+            _ => Tour::Main,
+        }
+    }
+
+    pub fn steps(&self) -> &'static [TourStep] {
+        match self {
+            Tour::Main => &[
+                TourStep::Recipients,
+                TourStep::OtherButton,
+                TourStep::OtherDialog,
+                TourStep::FilePicker,
+                TourStep::Send,
+            ],
+            Tour::Settings => &[
+                TourStep::SelectPerson,
+                TourStep::EditPerson,
+                TourStep::SaveSettings,
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TourStep {
+    Recipients,
+    OtherButton,
+    OtherDialog,
+    FilePicker,
+    Send,
+    SelectPerson,
+    EditPerson,
+    SaveSettings,
+}
+
+impl TourStep {
+    pub fn tour(&self) -> Tour {
+        match self {
+            TourStep::SelectPerson | TourStep::EditPerson | TourStep::SaveSettings => {
+                Tour::Settings
+            }
+            _ => Tour::Main,
+        }
+    }
+
+    //css selector of highlighted element
+    pub fn target(&self) -> &'static str {
+        match self {
+            TourStep::Recipients => ".center-buttons",
+            TourStep::OtherButton => ".other-button",
+            TourStep::OtherDialog => "#overlay-other .overlay-window",
+            TourStep::FilePicker => ".file-picker",
+            TourStep::Send => "#truck",
+            TourStep::SelectPerson => ".center-buttons",
+            TourStep::EditPerson => "#bottom-bar",
+            TourStep::SaveSettings => ".settings-bottom-button.save",
+        }
+    }
+
+    pub fn message_key(&self) -> &'static str {
+        match self {
+            TourStep::Recipients => "tutorial-recipients",
+            TourStep::OtherButton => "tutorial-other-button",
+            TourStep::OtherDialog => "tutorial-other-dialog",
+            TourStep::FilePicker => "tutorial-file-picker",
+            TourStep::Send => "tutorial-send",
+            TourStep::SelectPerson => "tutorial-select-person",
+            TourStep::EditPerson => "tutorial-edit-person",
+            TourStep::SaveSettings => "tutorial-save-settings",
+        }
+    }
+}
+
+//only finished tours are remembered, unfinished tour starts again from beginning
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Tutorial {
+    main_done: bool,
+    settings_done: bool,
+    #[serde(skip)]
+    main_step: usize,
+    #[serde(skip)]
+    settings_step: usize,
+}
+
+impl Tutorial {
+    pub fn load_tutorial() -> Tutorial {
+        match std::fs::read_to_string("tutorial.ron") {
+            Ok(ron_string) => ron::de::from_str(&ron_string).unwrap_or_default(),
+            Err(_) => Tutorial::default(),
+        }
+    }
+
+    pub fn save_tutorial(&self) {
+        let ron_string =
+            ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).unwrap();
+
+        std::fs::write("tutorial.ron", ron_string).unwrap();
+    }
+
+    pub fn current_step(&self, tour: Tour) -> Option<TourStep> {
+        let (done, step) = self.progress(tour);
+
+        if done {
+            return None;
+        }
+
+        tour.steps().get(step).copied()
+    }
+
+    //step numbers are shown from one
+    pub fn step_number(&self, tour: Tour) -> usize {
+        self.progress(tour).1 + 1
+    }
+
+    //user did what the step asks, later step jumps over steps the user didn't need
+    pub fn complete(&mut self, step: TourStep) {
+        let tour = step.tour();
+
+        if self.current_step(tour).is_none() {
+            return;
+        }
+
+        let position = tour.steps().iter().position(|tour_step| *tour_step == step).unwrap();
+
+        if position < self.progress(tour).1 {
+            return;
+        }
+
+        if position + 1 == tour.steps().len() {
+            self.finish(tour);
+        } else {
+            *self.step_mut(tour) = position + 1;
+        }
+    }
+
+    pub fn skip(&mut self, tour: Tour) {
+        self.finish(tour);
+    }
+
+    pub fn restart(&mut self, tour: Tour) {
+        *self.step_mut(tour) = 0;
+        *self.done_mut(tour) = false;
+        self.save_tutorial();
+    }
+
+    fn finish(&mut self, tour: Tour) {
+        *self.step_mut(tour) = 0;
+        *self.done_mut(tour) = true;
+        self.save_tutorial();
+    }
+
+    fn progress(&self, tour: Tour) -> (bool, usize) {
+        match tour {
+            Tour::Main => (self.main_done, self.main_step),
+            Tour::Settings => (self.settings_done, self.settings_step),
+        }
+    }
+
+    fn step_mut(&mut self, tour: Tour) -> &mut usize {
+        match tour {
+            Tour::Main => &mut self.main_step,
+            Tour::Settings => &mut self.settings_step,
+        }
+    }
+
+    fn done_mut(&mut self, tour: Tour) -> &mut bool {
+        match tour {
+            Tour::Main => &mut self.main_done,
+            Tour::Settings => &mut self.settings_done,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_action_skips_earlier_steps() {
+        let mut tutorial = Tutorial::default();
+
+        tutorial.complete(TourStep::OtherDialog);
+        assert_eq!(tutorial.current_step(Tour::Main), Some(TourStep::FilePicker));
+
+        //going back to earlier step doesn't rewind the tour
+        tutorial.complete(TourStep::Recipients);
+        assert_eq!(tutorial.current_step(Tour::Main), Some(TourStep::FilePicker));
+        assert_eq!(tutorial.step_number(Tour::Main), 4);
+    }
+
+    #[test]
+    fn tours_advance_separately() {
+        let mut tutorial = Tutorial::default();
+
+        tutorial.complete(TourStep::SelectPerson);
+
+        assert_eq!(tutorial.current_step(Tour::Main), Some(TourStep::Recipients));
+        assert_eq!(tutorial.current_step(Tour::Settings), Some(TourStep::EditPerson));
+    }
+}
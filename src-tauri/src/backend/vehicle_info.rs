@@ -0,0 +1,142 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use std::panic;
+use std::path::Path;
+
+use crate::backend::config::Config;
+
+//---------------------------
+
+const VIN_TRANSLITERATION: &str = "0123456789.ABCDEFGH..JKLMN.P.R..STUVWXYZ";
+const VIN_WEIGHTS: [u32; 17] = [8, 7, 6, 5, 4, 3, 2, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct VehicleInfo {
+    pub vin: String,
+    pub plate: String,
+    pub job_number: String,
+}
+
+impl VehicleInfo {
+    //fills only values which are still empty, so user corrections are kept
+    pub fn detect(&mut self, text: &str, config: &Config) {
+        if self.vin.is_empty() {
+            self.vin = find_vin(text).unwrap_or_default();
+        }
+        if self.plate.is_empty() {
+            self.plate = find_pattern(text, config.plate_pattern()).unwrap_or_default();
+        }
+        if self.job_number.is_empty() {
+            self.job_number = find_pattern(text, config.job_number_pattern()).unwrap_or_default();
+        }
+    }
+
+    //replaces {vin}, {spz} and {zakazka} in subject or body
+    pub fn fill_placeholders(&self, template: &str) -> String {
+        template
+            .replace("{vin}", &self.vin)
+            .replace("{spz}", &self.plate)
+            .replace("{zakazka}", &self.job_number)
+    }
+}
+
+pub fn extract_text(path: &Path) -> Option<String> {
+    if !path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("pdf"))
+    {
+        return None;
+    }
+
+    let content = std::fs::read(path).ok()?;
+
+    //pdf-extract panics on some malformed files
+    panic::catch_unwind(|| pdf_extract::extract_text_from_mem(&content).ok())
+        .ok()
+        .flatten()
+}
+
+fn find_vin(text: &str) -> Option<String> {
+    Regex::new(r"\b[A-HJ-NPR-Z0-9]{17}\b")
+        .unwrap()
+        .find_iter(&text.to_uppercase())
+        .map(|vin| vin.as_str().to_string())
+        .find(|vin| vin_is_valid(vin))
+}
+
+pub fn vin_is_valid(vin: &str) -> bool {
+    if vin.len() != 17 {
+        return false;
+    }
+
+    let mut sum = 0;
+
+    for (character, weight) in vin.chars().zip(VIN_WEIGHTS) {
+        let value = match character {
+            '0'..='9' => character.to_digit(10).unwrap(),
+            _ => match VIN_TRANSLITERATION.find(character) {
+                Some(position) if character != '.' => position as u32 % 10,
+                _ => return false,
+            },
+        };
+
+        sum += value * weight;
+    }
+
+    let check_digit = match sum % 11 {
+        10 => 'X',
+        rest => char::from_digit(rest, 10).unwrap(),
+    };
+
+    vin.chars().nth(8) == Some(check_digit)
+}
+
+//first capture group is used when pattern contains one
+fn find_pattern(text: &str, pattern: &str) -> Option<String> {
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let captures = Regex::new(pattern).ok()?.captures(text)?;
+
+    captures
+        .get(1)
+        .or(captures.get(0))
+        .map(|found| found.as_str().trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vin_check_digit_is_verified() {
+        assert!(vin_is_valid("1M8GDM9AXKP042788"));
+        assert!(!vin_is_valid("1M8GDM9A1KP042788"));
+        //I, O and Q are never used in vin
+        assert!(!vin_is_valid("1M8GDM9AXKP0427O8"));
+        assert!(!vin_is_valid("1M8GDM9AXKP04278"));
+
+        assert_eq!(
+            find_vin("VIN: 1m8gdm9axkp042788, chybný 1M8GDM9A1KP042788"),
+            Some("1M8GDM9AXKP042788".to_string())
+        );
+    }
+
+    #[test]
+    fn pattern_uses_first_capture_group() {
+        let text = "SPZ: 1AB 2345\nZakázka č. 2024-0815";
+
+        assert_eq!(
+            find_pattern(text, r"SPZ:\s*([0-9A-Z]{3}\s?[0-9]{4})"),
+            Some("1AB 2345".to_string())
+        );
+        assert_eq!(
+            find_pattern(text, r"\d{4}-\d{4}"),
+            Some("2024-0815".to_string())
+        );
+        assert_eq!(find_pattern(text, ""), None);
+        assert_eq!(find_pattern(text, "(neuzavřená"), None);
+    }
+}
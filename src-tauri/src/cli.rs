@@ -0,0 +1,386 @@
+use anyhow::Error;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use lettre::transport::smtp;
+use serde::Serialize;
+use tauri_plugin_dialog::FilePath;
+
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::backend::config::Config;
+use crate::backend::instance_lock;
+use crate::backend::mail_list_utils::{MailList, Person, MECHANIC_IDS, TECHNIC_IDS};
+use crate::backend::mail_sender::{message_id, MailSender, MailSenderError, ValidationProblem};
+use crate::backend::other_mail_utils::{address_problem, parse_mailboxes};
+use crate::backend::outbox::Outbox;
+use crate::backend::send_history::SendHistory;
+
+//---------------------------
+
+const EXIT_OTHER_ERROR: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_SEND_FAILED: i32 = 6;
+const EXIT_APPLICATION_RUNNING: i32 = 14;
+const EXIT_SETTINGS: i32 = 15;
+const EXIT_INVALID_ADDRESS: i32 = 16;
+
+#[derive(Parser)]
+#[command(
+    name = "mailsender",
+    about = "Without arguments the application window is opened."
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Send files without opening the window, with settings of the application.
+    /// The window must be closed, only dry run works while it is open
+    Send(SendArgs),
+}
+
+#[derive(Args)]
+struct SendArgs {
+    /// Roster name or e-mail address, can be repeated
+    #[arg(long = "to", value_name = "ROSTER-NAME|ADDRESS")]
+    to: Vec<String>,
+
+    /// Whole roster group, can be repeated
+    #[arg(long = "group", value_enum)]
+    group: Vec<Group>,
+
+    /// Overrides subject of sender profile, placeholders are filled in the same way
+    #[arg(long)]
+    subject: Option<String>,
+
+    /// Builds and checks the mail without sending it
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Prints result as JSON
+    #[arg(long)]
+    json: bool,
+
+    #[arg(required = true, value_name = "FILE")]
+    files: Vec<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Group {
+    Mechanics,
+    Technics,
+}
+
+impl Group {
+    fn ids(&self) -> Range<usize> {
+        match self {
+            Group::Mechanics => MECHANIC_IDS,
+            Group::Technics => TECHNIC_IDS,
+        }
+    }
+}
+
+#[derive(Serialize, Default)]
+struct SendReport {
+    sent: bool,
+    dry_run: bool,
+    subject: String,
+    recipients: Vec<String>,
+    files: Vec<String>,
+    message_id: String,
+    error: Option<String>,
+    exit_code: i32,
+}
+
+//None means no command was given and window should be opened
+pub fn run() -> Option<i32> {
+    let command = Cli::parse().command?;
+
+    match command {
+        Command::Send(args) => Some(send(args)),
+    }
+}
+
+fn send(args: SendArgs) -> i32 {
+    let mut report = SendReport {
+        dry_run: args.dry_run,
+        ..SendReport::default()
+    };
+
+    if let Err((exit_code, error)) = send_mail(&args, &mut report) {
+        report.exit_code = exit_code;
+        report.error = Some(error);
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else if let Some(error) = &report.error {
+        eprintln!("error: {error}");
+    } else {
+        println!(
+            "{}: {} -> {}",
+            if report.sent {
+                "sent"
+            } else {
+                "dry run, not sent"
+            },
+            report.subject,
+            report.recipients.join(", ")
+        );
+    }
+
+    report.exit_code
+}
+
+fn send_mail(args: &SendArgs, report: &mut SendReport) -> Result<(), (i32, String)> {
+    //history and outbox of running window would overwrite what is written here
+    let _instance_lock = if args.dry_run {
+        None
+    } else {
+        Some(instance_lock::acquire().ok_or((
+            EXIT_APPLICATION_RUNNING,
+            "application window is running, close it before sending".to_string(),
+        ))?)
+    };
+
+    let config = Config::try_load_config()
+        .map_err(|error| (EXIT_SETTINGS, format!("couldn't load config.ron: {error}")))?;
+    let mail_list = MailList::try_load_list().map_err(|error| {
+        (
+            EXIT_SETTINGS,
+            format!("couldn't load mail_list.ron: {error}"),
+        )
+    })?;
+
+    let (roster_people, other_people) = resolve_recipients(&mail_list, &args.to, &args.group)
+        .map_err(|recipient| (EXIT_USAGE, format!("unknown recipient {recipient}")))?;
+
+    let mut mail = MailSender::default();
+
+    for person in roster_people {
+        if !mail.has_person(&person) {
+            mail.add_person(person);
+        }
+    }
+
+    let skipped = mail.add_file(args.files.iter().cloned().map(FilePath::from).collect());
+
+    if !skipped.unreadable.is_empty() {
+        let error = MailSenderError::InvalidFilePath;
+
+        return Err((
+            exit_code(&error),
+            format!("{error}: {}", skipped.unreadable.join(", ")),
+        ));
+    }
+    mail.detect_vehicle_info(&config);
+    mail.set_subject(args.subject.clone());
+
+    report.subject = mail.subject(&config);
+    report.files = mail.files().iter().map(|file| file.file_name()).collect();
+
+    if let Some(problem) = mail.validate(&other_people, &config).into_iter().next() {
+        return Err(validation_failure(problem));
+    }
+
+    let message = if args.dry_run {
+        mail.build_message(&other_people, &config)
+            .map_err(|error| failure(&error))?
+    } else {
+        let (history_entry, message) = mail
+            .send(other_people, config.clone())
+            .map_err(|error| failure(&error))?;
+
+        //mail is already sent, failed copy waits in outbox for the application
+        if config.imap().saves_sent_copy() {
+            let mut outbox = Outbox::load_outbox();
+            outbox.add_copy(&history_entry.message_id, &message.formatted());
+            Outbox::flush(&Mutex::new(outbox), config.imap()).ok();
+        }
+
+        SendHistory::load_history().add_entry(history_entry);
+        report.sent = true;
+
+        message
+    };
+
+    report.message_id = message_id(&message);
+    report.recipients = message
+        .envelope()
+        .to()
+        .iter()
+        .map(|address| address.to_string())
+        .collect();
+
+    Ok(())
+}
+
+//roster names are matched first, anything else has to be an address; returns the unknown value
+fn resolve_recipients(
+    mail_list: &MailList,
+    to: &[String],
+    groups: &[Group],
+) -> Result<(Vec<Person>, Vec<Person>), String> {
+    let mut roster_people: Vec<Person> = groups
+        .iter()
+        .flat_map(|group| group.ids())
+        .filter_map(|id| mail_list.load_person(id))
+        .collect();
+    let mut other_people: Vec<Person> = vec![];
+
+    for recipient in to {
+        let roster_person = (MECHANIC_IDS.start..TECHNIC_IDS.end)
+            .filter_map(|id| mail_list.load_person(id))
+            .find(|person| person.name.trim().eq_ignore_ascii_case(recipient.trim()));
+
+        match roster_person {
+            Some(person) => roster_people.push(person),
+            None => {
+                let people = parse_mailboxes(recipient);
+
+                if people.is_empty()
+                    || people
+                        .iter()
+                        .any(|person| address_problem(&person.mail).is_some())
+                {
+                    return Err(recipient.clone());
+                }
+
+                other_people.extend(people);
+            }
+        }
+    }
+
+    Ok((roster_people, other_people))
+}
+
+fn exit_code(error: &MailSenderError) -> i32 {
+    match error {
+        MailSenderError::InvalidFilePath => 3,
+        MailSenderError::NoRecipients => 4,
+        MailSenderError::NoFile => 5,
+        MailSenderError::CouldntSendEmail(_) => EXIT_SEND_FAILED,
+        MailSenderError::InvalidMessage => 7,
+        MailSenderError::NoRemoteConnection => 8,
+        MailSenderError::CouldntMergePdf => 9,
+        MailSenderError::CouldntGetAccessToken => 10,
+        MailSenderError::CouldntSignMessage => 11,
+        MailSenderError::CouldntProtectMessage => 12,
+        MailSenderError::MissingRecipientCertificate(_) => 13,
+    }
+}
+
+//errors from smtp or address parsing aren't MailSenderError
+fn failure(error: &Error) -> (i32, String) {
+    let exit_code = if let Some(error) = error.downcast_ref::<MailSenderError>() {
+        exit_code(error)
+    } else if let Some(error) = error.downcast_ref::<smtp::Error>() {
+        smtp_exit_code(error)
+    } else {
+        EXIT_OTHER_ERROR
+    };
+
+    (exit_code, error.to_string())
+}
+
+//server answered and refused the mail, otherwise it couldn't be reached
+fn smtp_exit_code(error: &smtp::Error) -> i32 {
+    if error.is_permanent() || error.is_transient() || error.is_response() || error.is_client() {
+        EXIT_SEND_FAILED
+    } else {
+        exit_code(&MailSenderError::NoRemoteConnection)
+    }
+}
+
+fn validation_failure(problem: ValidationProblem) -> (i32, String) {
+    match problem {
+        ValidationProblem::NoFile => (exit_code(&MailSenderError::NoFile), "no file".to_string()),
+        ValidationProblem::NoRecipients => (
+            exit_code(&MailSenderError::NoRecipients),
+            "no recipients".to_string(),
+        ),
+        ValidationProblem::InvalidAddress { mail, reason } => (
+            EXIT_INVALID_ADDRESS,
+            format!("invalid address {mail}: {reason}"),
+        ),
+        ValidationProblem::MissingCertificate { mail, reason } => (
+            exit_code(&MailSenderError::MissingRecipientCertificate(mail.clone())),
+            format!("recipient {mail}: {reason}"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mail_list() -> MailList {
+        let mut list = vec!["None"; 29];
+        list[0] = r#"Some((name: "Jan Novák", mail: "novak@servis.cz"))"#;
+        list[1] = r#"Some((name: "Petr Svoboda", mail: "svoboda@servis.cz"))"#;
+        list[24] = r#"Some((name: "Eva Dvořáková", mail: "dvorakova@servis.cz"))"#;
+
+        ron::de::from_str(&format!("(list: [{}])", list.join(", "))).unwrap()
+    }
+
+    #[test]
+    fn recipients_are_resolved_from_roster_groups_and_addresses() {
+        let (roster_people, other_people) = resolve_recipients(
+            &mail_list(),
+            &[
+                "jan novák".to_string(),
+                "Dílna <dilna@firma.cz>".to_string(),
+            ],
+            &[Group::Technics],
+        )
+        .unwrap();
+
+        let roster_names: Vec<&str> = roster_people
+            .iter()
+            .map(|person| person.name.as_str())
+            .collect();
+
+        assert_eq!(roster_names, ["Eva Dvořáková", "Jan Novák"]);
+        assert_eq!(other_people.len(), 1);
+        assert_eq!(other_people[0].mail, "dilna@firma.cz");
+    }
+
+    #[test]
+    fn unknown_recipient_is_reported() {
+        assert_eq!(
+            resolve_recipients(&mail_list(), &["Jan Novotný".to_string()], &[]),
+            Err("Jan Novotný".to_string())
+        );
+    }
+
+    #[test]
+    fn arguments_are_parsed() {
+        let cli = Cli::try_parse_from([
+            "mailsender",
+            "send",
+            "--to",
+            "Jan Novák",
+            "--group",
+            "mechanics",
+            "--dry-run",
+            "--json",
+            "protokol.pdf",
+        ])
+        .unwrap();
+
+        let Some(Command::Send(args)) = cli.command else {
+            panic!("send command expected");
+        };
+
+        assert_eq!(args.to, ["Jan Novák"]);
+        assert!(args.dry_run && args.json);
+        assert_eq!(args.files, [PathBuf::from("protokol.pdf")]);
+        assert!(Cli::try_parse_from(["mailsender"])
+            .unwrap()
+            .command
+            .is_none());
+    }
+}
@@ -0,0 +1,99 @@
+use maud::{html, Markup};
+use tauri::Manager;
+
+use crate::AppState;
+use crate::backend::locale::tr;
+
+//---------------------------
+
+#[tauri::command]
+pub fn open_settings_addresses(app: tauri::AppHandle) -> String {
+    let markup: Markup = html! {
+        div #overlay-settings-addresses .overlay{
+            div.overlay-window{
+                button.close-button
+                hx-post="command:close_settings_addresses"
+                hx-trigger="click"
+                hx-target="#overlay-settings-addresses"
+                hx-swap="outerHTML"
+                {("X")}
+                h1.overlay-title{(tr("addresses-title"))}
+                div.config-row-section #settings-address-rows{
+                    (render_address_rows(&app))
+                }
+            }
+        }
+    };
+
+    markup.into_string()
+}
+
+#[tauri::command]
+pub fn close_settings_addresses() -> String {
+    let markup: Markup = html! {
+        div #settings-addresses-placeholder {}
+    };
+
+    markup.into_string()
+}
+
+#[tauri::command]
+pub fn purge_address(app: tauri::AppHandle, mail: String) -> String {
+    let app_state = app.state::<AppState>();
+
+    let mut address_suggestions = app_state.address_suggestions.lock().unwrap();
+    address_suggestions.purge(&mail);
+    address_suggestions.save_suggestions();
+    drop(address_suggestions);
+
+    render_address_rows(&app).into_string()
+}
+
+#[tauri::command]
+pub fn toggle_block_address(app: tauri::AppHandle, mail: String) -> String {
+    let app_state = app.state::<AppState>();
+
+    let mut address_suggestions = app_state.address_suggestions.lock().unwrap();
+    address_suggestions.toggle_block(&mail);
+    address_suggestions.save_suggestions();
+    drop(address_suggestions);
+
+    render_address_rows(&app).into_string()
+}
+
+fn render_address_rows(app: &tauri::AppHandle) -> Markup {
+    let app_state = app.state::<AppState>();
+    let address_suggestions = app_state.address_suggestions.lock().unwrap();
+
+    html! {
+        @for address in address_suggestions.addresses() {
+            div.config-row{
+                h1.config-row-title .blocked-address[address.blocked]
+                {(address.mail) " (" (address.count) "x)"}
+                div.address-row-buttons{
+                    button.remove-button
+                    hx-post="command:toggle_block_address"
+                    hx-trigger="click"
+                    hx-target="#settings-address-rows"
+                    hx-swap="innerHTML"
+                    hx-vals=(serde_json::json!({ "mail": address.mail }).to_string())
+                    {
+                        @if address.blocked {
+                            (tr("addresses-unblock"))
+                        }
+                        @else {
+                            (tr("addresses-block"))
+                        }
+                    }
+                    button.remove-button
+                    hx-post="command:purge_address"
+                    hx-trigger="click"
+                    hx-target="#settings-address-rows"
+                    hx-swap="innerHTML"
+                    hx-vals=(serde_json::json!({ "mail": address.mail }).to_string())
+                    {(tr("addresses-delete"))}
+                }
+            }
+        }
+    }
+}
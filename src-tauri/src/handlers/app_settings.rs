@@ -2,7 +2,10 @@ use maud::{html, Markup};
 use tauri::Manager;
 
 use crate::AppState;
+use crate::backend::locale::tr;
 use crate::backend::mail_list_utils;
+use crate::backend::tutorial::{Tour, TourStep};
+use crate::handlers::tutorial::render_tutorial;
 use crate::MailList;
 
 //---------------------------
@@ -18,16 +21,16 @@ pub fn open_settings_password() -> String {
                 hx-target="#overlay-password"
                 hx-swap="outerHTML"
                 {("X")}
-                h1.password-title{("Zadejte prosím heslo pro vstup do nastavení")}
+                h1.password-title{(tr("settings-password-title"))}
                 input.password-input
-                placeholder="Heslo"
+                placeholder=(tr("settings-password-placeholder"))
                 {}
                 button.password-check-button.save
                 hx-post="command:open_settings"
                 hx-trigger="click"
                 hx-target="#app-body"
                 hx-swap="outerHTML"
-                {("ověřit")}
+                {(tr("settings-password-check"))}
             }
         }
     };
@@ -48,8 +51,12 @@ pub fn close_settings_password() -> String {
 pub fn open_settings(app: tauri::AppHandle) -> String {
     let app_state = app.state::<AppState>();
 
-    app_state.mail.lock().unwrap().clear();
-    app_state.other_mail_list.lock().unwrap().clear();
+    if app_state.config.lock().unwrap().keep_recipients() {
+        app_state.mail.lock().unwrap().clear_files();
+    } else {
+        app_state.mail.lock().unwrap().clear();
+        app_state.other_mail_list.lock().unwrap().clear();
+    }
 
     let markup: Markup = html! {
         body #app-body {
@@ -60,19 +67,25 @@ pub fn open_settings(app: tauri::AppHandle) -> String {
                     hx-trigger="click"
                     hx-target="#settings-config-placeholder"
                     hx-swap="outerHTML"
-                    {("config")}
+                    {(tr("settings-config"))}
+                    button.top-bar-button
+                    hx-post="command:open_settings_addresses"
+                    hx-trigger="click"
+                    hx-target="#settings-addresses-placeholder"
+                    hx-swap="outerHTML"
+                    {(tr("saved-addresses"))}
                     button.top-bar-button
                     hx-post="command:open_feedback"
                     hx-trigger="click"
                     hx-target="#feedback-placeholder"
                     hx-swap="outerHTML"
-                    {("hlášení chyb a nápady na vylepšení")}
+                    {(tr("feedback"))}
                     button.top-bar-button
                     hx-post="command:open_settings_manual"
                     hx-trigger="click"
                     hx-target="#settings-manual-placeholder"
                     hx-swap="outerHTML"
-                    {("návod k použití")}
+                    {(tr("manual"))}
                 }
                 img.man-logo
                 src="src/assets/man_logo_batch.svg"
@@ -95,11 +108,13 @@ pub fn open_settings(app: tauri::AppHandle) -> String {
             div #feedback-placeholder{}
             div #settings-manual-placeholder{}
             div #settings-config-placeholder{}
+            div #settings-addresses-placeholder{}
             div #valid-mail-placeholder{}
             div #discard-overlay-placeholder {}
+            (render_tutorial(Tour::Settings))
             div.bottom-bar #bottom-bar{
             div.bottom-part-settings-names{
-                h1.settings-bottom-text{("Vyberte prosím osobu pro úpravu údajů")}
+                h1.settings-bottom-text{(tr("settings-select-person"))}
             }
             div.bottom-part-settings-names{
             }
@@ -109,13 +124,13 @@ pub fn open_settings(app: tauri::AppHandle) -> String {
                 hx-trigger="click"
                 hx-target="#valid-mail-placeholder"
                 hx-swap="outerHTML"
-                {("uložit a zavřít")}
+                {(tr("save-and-close"))}
                 button.settings-bottom-button.close
                 hx-post="command:open_discard_overlay"
                 hx-trigger="click"
                 hx-target="#discard-overlay-placeholder"
                 hx-swap="outerHTML"
-                {("zavřít bez uložení")}
+                {(tr("close-without-saving"))}
             }
             }
         }
@@ -135,19 +150,19 @@ pub fn open_discard_overlay()->String{
                 hx-target="#discard-overlay"
                 hx-swap="outerHTML"
                 {("X")}
-                h1.discard-overlay-title{("Opravdu si přejete odejít bez uložení?")}
+                h1.discard-overlay-title{(tr("discard-title"))}
                 button.discard-overlay-back.save
                 hx-post="command:close_discard_overlay"
                 hx-trigger="click"
                 hx-target="#discard-overlay"
                 hx-swap="outerHTML"
-                {("návrat zpět do nastavení")}
+                {(tr("discard-back"))}
                 button.discard-overlay-discard.close
                 hx-post="command:discard_and_close_settings"
                 hx-trigger="click"
                 hx-target="#app-body"
                 hx-swap="outerHTML"
-                {("odejít bez uložení")}
+                {(tr("discard-leave"))}
             }
         }
     }.into_string()
@@ -162,11 +177,9 @@ pub fn close_discard_overlay()->String{
 
 #[tauri::command]
 pub fn discard_and_close_settings(app: tauri::AppHandle) -> String {
-    let app_state = app.state::<AppState>();
-
-    *app_state.mail_list.lock().unwrap() = MailList::load_list();
+    *app.state::<AppState>().mail_list.lock().unwrap() = MailList::load_list();
 
-    close_settings()
+    close_settings(app)
 }
 
 #[tauri::command]
@@ -179,6 +192,8 @@ pub fn save_and_close_settings(app: tauri::AppHandle) -> String {
         wrong_mail_warning(mail_list_save.unwrap_err())
     }
     else {
+        app_state.tutorial.lock().unwrap().complete(TourStep::SaveSettings);
+
         html!{
             div
             hx-trigger="load delay:1ms"
@@ -200,13 +215,13 @@ pub fn wrong_mail_warning(name_list: Vec<String>) -> String {
                 hx-target="#wrong-mail-warning-overlay"
                 hx-swap="outerHTML"
                 {("X")}
-                h1.overlay-title{("Následující osoby mají neplatný E-mail")}
+                h1.overlay-title{(tr("invalid-mail-title"))}
                 div.mail-warning-rows-section{
                     @for name in (name_list){
                         h2.mail-warning-row{(name)};
                     }
                 }
-                h1.overlay-title{("Upravte nebo smažte je")}
+                h1.overlay-title{(tr("invalid-mail-hint"))}
 
             }
         }
@@ -221,29 +236,67 @@ pub fn close_wrong_mail_warning() -> String{
 }
 
 #[tauri::command]
-pub fn close_settings() -> String {
-    let markup: Markup = html! {
+pub fn close_settings(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+
+    //kept recipients could be removed from roster in settings
+    let mail_list = app_state.mail_list.lock().unwrap();
+
+    let roster: Vec<mail_list_utils::Person> = (mail_list_utils::MECHANIC_IDS.start
+        ..mail_list_utils::TECHNIC_IDS.end)
+        .filter_map(|id| mail_list.load_person(id))
+        .collect();
+
+    let mut mail = app_state.mail.lock().unwrap();
+
+    mail.retain_people(&roster);
+
+    if mail.author().is_some_and(|author| !roster.contains(author)) {
+        mail.set_author(None);
+    }
+
+    render_main_screen().into_string()
+}
+
+//main screen is rendered here instead of index.html, so it follows selected language
+#[tauri::command]
+pub fn load_main_screen() -> String {
+    render_main_screen().into_string()
+}
+
+fn render_main_screen() -> Markup {
+    html! {
         body #app-body {
             div.top-bar{
+                div.author-select-section
+                hx-trigger="load delay:1ms"
+                hx-swap="innerHTML"
+                hx-post="command:load_author_select"
+                {}
+                div.profile-select-section
+                hx-trigger="load delay:1ms"
+                hx-swap="innerHTML"
+                hx-post="command:load_profile_select"
+                {}
                 div.top-button-bar{
                     button.top-bar-button
                     hx-post="command:open_settings_password"
                     hx-trigger="click"
                     hx-target="#settings-placeholder"
                     hx-swap="outerHTML"
-                    {("nastavení")}
+                    {(tr("settings"))}
                     button.top-bar-button
                     hx-post="command:open_feedback"
                     hx-trigger="click"
                     hx-target="#feedback-placeholder"
                     hx-swap="outerHTML"
-                    {("hlášení chyb a nápady na vylepšení")}
+                    {(tr("feedback"))}
                     button.top-bar-button
                     hx-post="command:open_manual"
                     hx-trigger="click"
                     hx-target="#manual-placeholder"
                     hx-swap="outerHTML"
-                    {("návod k použití")}
+                    {(tr("manual"))}
                 }
                 img.man-logo
                 src="src/assets/man_logo_batch.svg"
@@ -262,28 +315,43 @@ pub fn close_settings() -> String {
                 hx-post="command:load_technics"
                 {}
             }
+            div.report-cards #report-cards
+            hx-trigger="load delay:1ms, every 5s"
+            hx-swap="innerHTML"
+            hx-post="command:load_new_reports"
+            {}
+            div #mailbox-alerts
+            hx-trigger="load delay:1ms, every 5s"
+            hx-swap="innerHTML"
+            hx-post="command:load_mailbox_alerts"
+            {}
+            (render_tutorial(Tour::Main))
             div #overlay-other-placeholder{}
             div #feedback-placeholder{}
             div #manual-placeholder{}
             div #settings-placeholder{}
+            div #send-confirmation-placeholder{}
             div.bottom-bar{
-                button.file-picker
-                hx-trigger="click"
-                hx-post="command:pick_file"
-                hx-swap="outerHTML"
-                {("výběr souboru")}
-                input.truck
+                div.file-section #file-section {
+                    button.file-picker
+                    hx-trigger="click"
+                    hx-post="command:pick_file"
+                    hx-target="#file-section"
+                    hx-swap="outerHTML"
+                    {(tr("pick-files"))}
+                }
+                input.truck #truck
                 type="image"
                 src="src/assets/send_truck.svg"
                 alt="truck-icon"
                 hx-trigger="click"
-                hx-post="command:send"
+                hx-post="command:open_send_confirmation"
+                hx-target="#send-confirmation-placeholder"
                 hx-swap="outerHTML"
                 {}
             }
         }
-    };
-    markup.into_string()
+    }
 }
 
 #[tauri::command]
@@ -361,6 +429,8 @@ pub fn edit_person(id: String, app: tauri::AppHandle) -> String {
 
     let app_state = app.state::<AppState>();
 
+    app_state.tutorial.lock().unwrap().complete(TourStep::SelectPerson);
+
     let mail_list = app_state.mail_list.lock().unwrap();
 
     let person = match mail_list.load_person(id) {
@@ -368,13 +438,14 @@ pub fn edit_person(id: String, app: tauri::AppHandle) -> String {
         None => mail_list_utils::Person {
             name: "".to_string(),
             mail: "".to_string(),
+            certificate: "".to_string(),
         },
     };
 
     let markup: Markup = html! {
         div.bottom-bar #bottom-bar {
             div.bottom-part-settings-names{
-                h1.settings-bottom-text{("jméno")}
+                h1.settings-bottom-text{(tr("person-name"))}
                 input.settings-bottom-input
                 type="text"
                 hx-post="command:edit_person_name"
@@ -385,7 +456,7 @@ pub fn edit_person(id: String, app: tauri::AppHandle) -> String {
                 {}
             }
             div.bottom-part-settings-names{
-                h1.settings-bottom-text{("e-mail")}
+                h1.settings-bottom-text{(tr("person-mail"))}
                 input.settings-bottom-input
                 type="text"
                 hx-post="command:edit_person_mail"
@@ -395,19 +466,31 @@ pub fn edit_person(id: String, app: tauri::AppHandle) -> String {
                 value=(person.mail)
                 {}
             }
+            div.bottom-part-settings-names{
+                h1.settings-bottom-text{(tr("person-certificate"))}
+                input.settings-bottom-input
+                type="text"
+                hx-post="command:edit_person_certificate"
+                name="text"
+                hx-trigger="change"
+                hx-vals={(format!(r#""id": {id}"#))}
+                placeholder=(tr("person-certificate-placeholder"))
+                value=(person.certificate)
+                {}
+            }
             div.bottom-part-settings-buttons{
                 button.settings-bottom-button.save
                 hx-post="command:save_and_close_settings"
                 hx-trigger="click"
                 hx-target="#valid-mail-placeholder"
                 hx-swap="outerHTML"
-                {("uložit a zavřít")}
+                {(tr("save-and-close"))}
                 button.settings-bottom-button.close
                 hx-post="command:open_discard_overlay"
                 hx-trigger="click"
                 hx-target="#discard-overlay-placeholder"
                 hx-swap="outerHTML"
-                {("zavřít bez uložení")}
+                {(tr("close-without-saving"))}
 
             }
         }
@@ -447,6 +530,7 @@ pub fn mark_person(id: String, app: tauri::AppHandle) -> String {
         None => mail_list_utils::Person {
             name: "".to_string(),
             mail: "".to_string(),
+            certificate: "".to_string(),
         },
     };
 
@@ -472,6 +556,7 @@ pub fn unmark_person(id: String, app: tauri::AppHandle) -> String {
         None => mail_list_utils::Person {
             name: "".to_string(),
             mail: "".to_string(),
+            certificate: "".to_string(),
         },
     };
 
@@ -500,6 +585,8 @@ pub fn edit_person_name(app: tauri::AppHandle, id: String, text: String) {
         .lock()
         .unwrap()
         .save_person_name(id, text);
+
+    app_state.tutorial.lock().unwrap().complete(TourStep::EditPerson);
 }
 
 #[tauri::command]
@@ -513,4 +600,19 @@ pub fn edit_person_mail(app: tauri::AppHandle, id: String, text: String) {
         .lock()
         .unwrap()
         .save_person_mail(id, text);
+
+    app_state.tutorial.lock().unwrap().complete(TourStep::EditPerson);
+}
+
+#[tauri::command]
+pub fn edit_person_certificate(app: tauri::AppHandle, id: String, text: String) {
+    let id: usize = id.parse().unwrap();
+
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .mail_list
+        .lock()
+        .unwrap()
+        .save_person_certificate(id, text);
 }
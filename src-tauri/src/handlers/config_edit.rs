@@ -2,7 +2,11 @@ use maud::{html, Markup};
 use tauri::Manager;
 
 use crate::AppState;
-use crate::backend::config::Config;
+use crate::backend::config::{Config, OAuthSettings, PostSendAction, SenderAuth};
+use crate::backend::dkim;
+use crate::backend::file_list_utils;
+use crate::backend::locale::{tr, Locale};
+use crate::backend::smime;
 
 //---------------------------
 
@@ -11,7 +15,12 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
     let app_state = app.state::<AppState>();
     let config = app_state.config.lock().unwrap().clone();
 
-    let markup: Markup = html! {
+    render_settings_config(&config, None).into_string()
+}
+
+//problem is shown above save button when config can't be saved
+fn render_settings_config(config: &Config, problem: Option<&str>) -> Markup {
+    html! {
         div #overlay-settings-config .overlay{
             div.overlay-window{
                 button.close-button
@@ -20,11 +29,29 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                 hx-target="#overlay-settings-config"
                 hx-swap="outerHTML"
                 {("X")}
-                h1.overlay-title{("úprava konfiguračního souboru")}
+                h1.overlay-title{(tr("config-title"))}
                 div.config-row-section{
                     div.config-row{
                         h1.config-row-title
-                        {("Jméno odesilatele:")}
+                        {(tr("config-locale"))}
+                        select.config-row-input-field
+                        hx-post="command:save_locale"
+                        hx-trigger="change"
+                        hx-target="#overlay-settings-config"
+                        hx-swap="outerHTML"
+                        name="text"
+                        {
+                            @for locale in Locale::ALL {
+                                option
+                                value=(locale.code())
+                                selected[locale == config.locale()]
+                                {(locale.name())}
+                            }
+                        }
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-sender-name"))}
                         input.config-row-input-field
                         type="text"
                         hx-post="command:save_sender_name"
@@ -35,7 +62,7 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                     }
                     div.config-row{
                         h1.config-row-title
-                        {("E-mail odesilatele:")}
+                        {(tr("config-sender-mail"))}
                         input.config-row-input-field
                         type="text"
                         hx-post="command:save_sender_mail"
@@ -46,7 +73,7 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                     }
                     div.config-row{
                         h1.config-row-title
-                        {("heslo odesilatele:")}
+                        {(tr("config-sender-password"))}
                         input.config-row-input-field
                         type="text"
                         hx-post="command:save_sender_password"
@@ -55,9 +82,10 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                         value=(config.sender_password())
                         {}
                     }
+                    (render_auth_rows("edit_sender_auth", "", config.sender_auth(), config.sender_oauth()))
                     div.config-row{
                         h1.config-row-title
-                        {("předmět E-mailu:")}
+                        {(tr("config-title-template"))}
                         input.config-row-input-field
                         type="text"
                         hx-post="command:save_title"
@@ -68,7 +96,18 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                     }
                     div.config-row{
                         h1.config-row-title
-                        {("smtp transport:")}
+                        {(tr("config-body"))}
+                        input.config-row-input-field
+                        type="text"
+                        hx-post="command:save_body"
+                        hx-trigger="change"
+                        name="text"
+                        value=(config.body())
+                        {}
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-smtp-transport"))}
                         input.config-row-input-field
                         type="text"
                         hx-post="command:save_smtp_transport"
@@ -79,7 +118,7 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                     }
                     div.config-row{
                         h1.config-row-title
-                        {("feedback E-mail:")}
+                        {(tr("config-feedback-mail"))}
                         input.config-row-input-field
                         type="text"
                         hx-post="command:save_feedback_mail"
@@ -90,7 +129,7 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                     }
                     div.config-row{
                         h1.config-row-title
-                        {("feedback příjemce:")}
+                        {(tr("config-feedback-recepient"))}
                         input.config-row-input-field
                         type="text"
                         hx-post="command:save_feedback_recepient"
@@ -101,7 +140,7 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                     }
                     div.config-row{
                         h1.config-row-title
-                        {("feedback předmět:")}
+                        {(tr("config-feedback-subject"))}
                         input.config-row-input-field
                         type="text"
                         hx-post="command:save_feedback_subject"
@@ -112,7 +151,7 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                     }
                     div.config-row{
                         h1.config-row-title
-                        {("heslo nastavení:")}
+                        {(tr("config-settings-password"))}
                         input.config-row-input-field
                         type="text"
                         hx-post="command:save_settings_password"
@@ -121,6 +160,280 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                         value=(config.settings_password())
                         {}
                     }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-watch-directory"))}
+                        input.config-row-input-field
+                        type="text"
+                        hx-post="command:save_watch_directory"
+                        hx-trigger="change"
+                        name="text"
+                        value=(config.watch_directory())
+                        {}
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-watch-pattern"))}
+                        input.config-row-input-field
+                        type="text"
+                        hx-post="command:save_watch_pattern"
+                        hx-trigger="change"
+                        name="text"
+                        value=(config.watch_pattern())
+                        {}
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-watch-max-age"))}
+                        input.config-row-input-field
+                        type="number"
+                        min="0"
+                        hx-post="command:save_watch_max_age_hours"
+                        hx-trigger="change"
+                        name="text"
+                        value=(config.watch_max_age_hours())
+                        {}
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-watch-archive"))}
+                        select.config-row-input-field
+                        hx-post="command:save_watch_archive"
+                        hx-trigger="change"
+                        name="text"
+                        {
+                            option value="ano" selected[config.watch_archive()] {(tr("yes"))}
+                            option value="ne" selected[!config.watch_archive()] {(tr("no"))}
+                        }
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-post-send-action"))}
+                        select.config-row-input-field
+                        hx-post="command:save_post_send_action"
+                        hx-trigger="change"
+                        name="text"
+                        {
+                            option
+                            value="ponechat"
+                            selected[config.post_send_action() == PostSendAction::Leave]
+                            {(tr("config-post-send-leave"))}
+                            option
+                            value="presunout"
+                            selected[config.post_send_action() == PostSendAction::Move]
+                            {(tr("config-post-send-move"))}
+                            option
+                            value="prejmenovat"
+                            selected[config.post_send_action() == PostSendAction::Rename]
+                            {(tr("config-post-send-rename"))}
+                        }
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-archive-directory"))}
+                        input.config-row-input-field
+                        type="text"
+                        hx-post="command:save_archive_directory"
+                        hx-trigger="change"
+                        name="text"
+                        value=(config.archive_directory())
+                        {}
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-plate-pattern"))}
+                        input.config-row-input-field
+                        type="text"
+                        hx-post="command:save_plate_pattern"
+                        hx-trigger="change"
+                        name="text"
+                        value=(config.plate_pattern())
+                        {}
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-job-number-pattern"))}
+                        input.config-row-input-field
+                        type="text"
+                        hx-post="command:save_job_number_pattern"
+                        hx-trigger="change"
+                        name="text"
+                        value=(config.job_number_pattern())
+                        {}
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-merge-file-name"))}
+                        input.config-row-input-field
+                        type="text"
+                        hx-post="command:save_merge_file_name"
+                        hx-trigger="change"
+                        name="text"
+                        value=(config.merge_file_name())
+                        {}
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-attachment-name"))}
+                        input.config-row-input-field
+                        type="text"
+                        hx-post="command:save_attachment_name_template"
+                        hx-trigger="change"
+                        name="text"
+                        placeholder="{datum}_{zakazka}_{spz}_{vin}_{odesilatel}_{nazev}_{poradi}"
+                        value=(config.attachment_name_template())
+                        {}
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-keep-recipients"))}
+                        select.config-row-input-field
+                        hx-post="command:save_keep_recipients"
+                        hx-trigger="change"
+                        name="text"
+                        {
+                            option value="ano" selected[config.keep_recipients()] {(tr("yes"))}
+                            option value="ne" selected[!config.keep_recipients()] {(tr("no"))}
+                        }
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-author-in-sender-name"))}
+                        select.config-row-input-field
+                        hx-post="command:save_author_in_sender_name"
+                        hx-trigger="change"
+                        name="text"
+                        {
+                            option value="ano" selected[config.author_in_sender_name()] {(tr("yes"))}
+                            option value="ne" selected[!config.author_in_sender_name()] {(tr("no"))}
+                        }
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-profile-selector"))}
+                        select.config-row-input-field
+                        hx-post="command:save_profile_selector"
+                        hx-trigger="change"
+                        name="text"
+                        {
+                            option value="ano" selected[config.profile_selector()] {(tr("yes"))}
+                            option value="ne" selected[!config.profile_selector()] {(tr("no"))}
+                        }
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-delivery-status"))}
+                        select.config-row-input-field
+                        hx-post="command:save_delivery_status"
+                        hx-trigger="change"
+                        name="text"
+                        {
+                            option value="ano" selected[config.delivery_status()] {(tr("yes"))}
+                            option value="ne" selected[!config.delivery_status()] {(tr("no"))}
+                        }
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-read-receipt"))}
+                        select.config-row-input-field
+                        hx-post="command:save_read_receipt"
+                        hx-trigger="change"
+                        name="text"
+                        {
+                            option value="ano" selected[config.read_receipt()] {(tr("yes"))}
+                            option value="ne" selected[!config.read_receipt()] {(tr("no"))}
+                        }
+                    }
+                    @for (field, title, value) in [
+                        ("selector", tr("config-dkim-selector"), &config.dkim().selector),
+                        ("domain", tr("config-dkim-domain"), &config.dkim().domain),
+                        ("private_key_path", tr("config-dkim-private-key"), &config.dkim().private_key_path),
+                    ] {
+                        div.config-row{
+                            h1.config-row-title
+                            {(title)}
+                            input.config-row-input-field
+                            type="text"
+                            hx-post="command:edit_dkim"
+                            hx-trigger="change"
+                            name="text"
+                            hx-vals={(format!(r#""field": "{field}""#))}
+                            value=(value)
+                            {}
+                        }
+                    }
+                    @for (field, title, value) in [
+                        ("certificate_path", tr("config-smime-certificate"), &config.smime().certificate_path),
+                        ("certificate_password", tr("config-smime-password"), &config.smime().certificate_password),
+                    ] {
+                        div.config-row{
+                            h1.config-row-title
+                            {(title)}
+                            input.config-row-input-field
+                            type="text"
+                            hx-post="command:edit_smime"
+                            hx-trigger="change"
+                            name="text"
+                            hx-vals={(format!(r#""field": "{field}""#))}
+                            value=(value)
+                            {}
+                        }
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-smime-encrypt"))}
+                        select.config-row-input-field
+                        hx-post="command:edit_smime"
+                        hx-trigger="change"
+                        name="text"
+                        hx-vals={(r#""field": "encrypt""#)}
+                        {
+                            option value="ano" selected[config.smime().encrypt] {(tr("yes"))}
+                            option value="ne" selected[!config.smime().encrypt] {(tr("no"))}
+                        }
+                    }
+                    @for (field, title, value) in [
+                        ("host", tr("config-imap-host"), config.imap().host.clone()),
+                        ("port", tr("config-imap-port"), config.imap().port.to_string()),
+                        ("username", tr("config-imap-username"), config.imap().username.clone()),
+                        ("password", tr("config-imap-password"), config.imap().password.clone()),
+                        ("folder", tr("config-imap-folder"), config.imap().folder.clone()),
+                        ("sent_folder", tr("config-imap-sent-folder"), config.imap().sent_folder.clone()),
+                    ] {
+                        div.config-row{
+                            h1.config-row-title
+                            {(title)}
+                            input.config-row-input-field
+                            type="text"
+                            hx-post="command:edit_imap"
+                            hx-trigger="change"
+                            name="text"
+                            hx-vals={(format!(r#""field": "{field}""#))}
+                            value=(value)
+                            {}
+                        }
+                    }
+                    div.config-row{
+                        h1.config-row-title
+                        {(tr("config-imap-tls"))}
+                        select.config-row-input-field
+                        hx-post="command:edit_imap"
+                        hx-trigger="change"
+                        name="text"
+                        hx-vals={(r#""field": "tls""#)}
+                        {
+                            option value="ano" selected[config.imap().tls] {(tr("yes"))}
+                            option value="ne" selected[!config.imap().tls] {(tr("no"))}
+                        }
+                    }
+                }
+                div.config-row-section #sender-profiles {
+                    (render_sender_profiles(config))
+                }
+                @if let Some(problem) = problem {
+                    div.mail-warning-rows-section {
+                        h2.mail-warning-row{(tr(problem))}
+                    }
                 }
                 div.bottom-button-row{
                     button.save-config.save
@@ -128,13 +441,22 @@ pub fn open_settings_config(app: tauri::AppHandle) -> String {
                     hx-trigger="click"
                     hx-target="#overlay-settings-config"
                     hx-swap="outerHTML"
-                    {("uložit a zavřít")}
+                    {(tr("save-and-close"))}
                 }
             }
         }
-    };
+    }
+}
 
-    markup.into_string()
+//overlay is rendered again in selected language
+#[tauri::command]
+pub fn save_locale(app: tauri::AppHandle, text: String) -> String {
+    let app_state = app.state::<AppState>();
+    let mut config = app_state.config.lock().unwrap();
+
+    config.save_locale(text);
+
+    render_settings_config(&config, None).into_string()
 }
 
 #[tauri::command]
@@ -208,11 +530,377 @@ pub fn save_settings_password(app: tauri::AppHandle, text: String) {
         .save_settings_password(text);
 }
 
+#[tauri::command]
+pub fn save_watch_directory(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state.config.lock().unwrap().save_watch_directory(text);
+}
+
+#[tauri::command]
+pub fn save_watch_pattern(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state.config.lock().unwrap().save_watch_pattern(text);
+}
+
+#[tauri::command]
+pub fn save_watch_max_age_hours(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .save_watch_max_age_hours(text.parse().unwrap_or_default());
+}
+
+#[tauri::command]
+pub fn save_watch_archive(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .save_watch_archive(text == "ano");
+}
+
+#[tauri::command]
+pub fn save_post_send_action(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    let action = match text.as_str() {
+        "presunout" => PostSendAction::Move,
+        "prejmenovat" => PostSendAction::Rename,
+        _ => PostSendAction::Leave,
+    };
+
+    app_state.config.lock().unwrap().save_post_send_action(action);
+}
+
+#[tauri::command]
+pub fn save_archive_directory(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state.config.lock().unwrap().save_archive_directory(text);
+}
+
+#[tauri::command]
+pub fn save_body(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state.config.lock().unwrap().save_body(text);
+}
+
+#[tauri::command]
+pub fn save_plate_pattern(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state.config.lock().unwrap().save_plate_pattern(text);
+}
+
+#[tauri::command]
+pub fn save_job_number_pattern(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .save_job_number_pattern(text);
+}
+
+#[tauri::command]
+pub fn save_merge_file_name(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state.config.lock().unwrap().save_merge_file_name(text);
+}
+
+#[tauri::command]
+pub fn save_attachment_name_template(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .save_attachment_name_template(text);
+}
+
+#[tauri::command]
+pub fn save_keep_recipients(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .save_keep_recipients(text == "ano");
+}
+
+#[tauri::command]
+pub fn save_author_in_sender_name(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .save_author_in_sender_name(text == "ano");
+}
+
+#[tauri::command]
+pub fn save_delivery_status(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .save_delivery_status(text == "ano");
+}
+
+#[tauri::command]
+pub fn save_read_receipt(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .save_read_receipt(text == "ano");
+}
+
+#[tauri::command]
+pub fn save_profile_selector(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .save_profile_selector(text == "ano");
+}
+
+#[tauri::command]
+pub fn save_default_profile(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+    let id: usize = text.parse().unwrap();
+
+    app_state.config.lock().unwrap().save_default_profile(id);
+}
+
+#[tauri::command]
+pub fn add_sender_profile(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+    let mut config = app_state.config.lock().unwrap();
+
+    config.add_sender_profile();
+
+    render_sender_profiles(&config).into_string()
+}
+
+#[tauri::command]
+pub fn remove_sender_profile(app: tauri::AppHandle, index: String) -> String {
+    let app_state = app.state::<AppState>();
+    let index: usize = index.parse().unwrap();
+    let mut mail = app_state.mail.lock().unwrap();
+    let mut config = app_state.config.lock().unwrap();
+
+    //profile ids start from one, zero is main sender
+    config.remove_sender_profile(index);
+    mail.remove_profile(index + 1);
+
+    render_sender_profiles(&config).into_string()
+}
+
+#[tauri::command]
+pub fn edit_sender_profile(app: tauri::AppHandle, index: String, field: String, text: String) {
+    let app_state = app.state::<AppState>();
+    let index: usize = index.parse().unwrap();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .edit_sender_profile(index, &field, text);
+}
+
+#[tauri::command]
+pub fn edit_sender_auth(app: tauri::AppHandle, field: String, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .config
+        .lock()
+        .unwrap()
+        .edit_sender_auth(&field, text);
+}
+
+#[tauri::command]
+pub fn edit_dkim(app: tauri::AppHandle, field: String, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state.config.lock().unwrap().edit_dkim(&field, text);
+}
+
+#[tauri::command]
+pub fn edit_smime(app: tauri::AppHandle, field: String, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state.config.lock().unwrap().edit_smime(&field, text);
+}
+
+#[tauri::command]
+pub fn edit_imap(app: tauri::AppHandle, field: String, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state.config.lock().unwrap().edit_imap(&field, text);
+}
+
+//oauth2 fields are used only when oauth2 login is selected
+fn render_auth_rows(command: &str, vals: &str, auth: SenderAuth, oauth: &OAuthSettings) -> Markup {
+    html! {
+        div.config-row{
+            h1.config-row-title
+            {(tr("config-sender-auth"))}
+            select.config-row-input-field
+            hx-post={"command:" (command)}
+            hx-trigger="change"
+            name="text"
+            hx-vals={(format!(r#"{vals}"field": "auth""#))}
+            {
+                option value="heslo" selected[auth == SenderAuth::Password] {(tr("config-auth-password"))}
+                option value="oauth2" selected[auth == SenderAuth::OAuth2] {(tr("config-auth-oauth2"))}
+            }
+        }
+        @for (field, title, value) in [
+            ("client_id", tr("config-oauth-client-id"), &oauth.client_id),
+            ("client_secret", tr("config-oauth-client-secret"), &oauth.client_secret),
+            ("refresh_token", tr("config-oauth-refresh-token"), &oauth.refresh_token),
+            ("token_endpoint", tr("config-oauth-token-endpoint"), &oauth.token_endpoint),
+        ] {
+            div.config-row{
+                h1.config-row-title
+                {(title)}
+                input.config-row-input-field
+                type="text"
+                hx-post={"command:" (command)}
+                hx-trigger="change"
+                name="text"
+                hx-vals={(format!(r#"{vals}"field": "{field}""#))}
+                value=(value)
+                {}
+            }
+        }
+    }
+}
+
+fn render_sender_profiles(config: &Config) -> Markup {
+    html! {
+        div.config-row{
+            h1.config-row-title
+            {(tr("config-default-profile"))}
+            select.config-row-input-field
+            hx-post="command:save_default_profile"
+            hx-trigger="change"
+            name="text"
+            {
+                @for id in 0..config.profile_count() {
+                    option
+                    value=(id)
+                    selected[id == config.default_profile()]
+                    {(config.profile(id).label)}
+                }
+            }
+        }
+        @for (index, profile) in config.sender_profiles().iter().enumerate() {
+            div.sender-profile {
+                div.sender-profile-header {
+                    h1.config-row-title{(tr("config-profile"))}
+                    button.remove-button
+                    hx-post="command:remove_sender_profile"
+                    hx-trigger="click"
+                    hx-target="#sender-profiles"
+                    hx-swap="innerHTML"
+                    hx-vals={(format!(r#""index": {index}"#))}
+                    {(tr("config-profile-remove"))}
+                }
+                @for (field, title, value) in [
+                    ("label", tr("config-profile-label"), &profile.label),
+                    ("sender_name", tr("config-sender-name"), &profile.sender_name),
+                    ("sender_mail", tr("config-sender-mail"), &profile.sender_mail),
+                    ("sender_password", tr("config-sender-password"), &profile.sender_password),
+                    ("smtp_transport", tr("config-smtp-transport"), &profile.smtp_transport),
+                    ("title", tr("config-title-template"), &profile.title),
+                    ("body", tr("config-body"), &profile.body),
+                ] {
+                    div.config-row{
+                        h1.config-row-title
+                        {(title)}
+                        input.config-row-input-field
+                        type="text"
+                        hx-post="command:edit_sender_profile"
+                        hx-trigger="change"
+                        name="text"
+                        hx-vals={(format!(r#""index": {index}, "field": "{field}""#))}
+                        value=(value)
+                        {}
+                    }
+                }
+                @for (field, title, value) in [
+                    ("delivery_status", tr("config-delivery-status"), profile.delivery_status),
+                    ("read_receipt", tr("config-read-receipt"), profile.read_receipt),
+                ] {
+                    div.config-row{
+                        h1.config-row-title
+                        {(title)}
+                        select.config-row-input-field
+                        hx-post="command:edit_sender_profile"
+                        hx-trigger="change"
+                        name="text"
+                        hx-vals={(format!(r#""index": {index}, "field": "{field}""#))}
+                        {
+                            option value="ano" selected[value] {(tr("yes"))}
+                            option value="ne" selected[!value] {(tr("no"))}
+                        }
+                    }
+                }
+                (render_auth_rows(
+                    "edit_sender_profile",
+                    &format!(r#""index": {index}, "#),
+                    profile.auth,
+                    &profile.oauth,
+                ))
+            }
+        }
+        div.bottom-button-row{
+            button.add-button
+            hx-post="command:add_sender_profile"
+            hx-trigger="click"
+            hx-target="#sender-profiles"
+            hx-swap="innerHTML"
+            {(tr("config-profile-add"))}
+        }
+    }
+}
+
 #[tauri::command]
 pub fn save_and_close_settings_config(app: tauri::AppHandle) -> String {
     let app_state = app.state::<AppState>();
+    let config = app_state.config.lock().unwrap();
 
-    app_state.config.lock().unwrap().save_config();
+    //unusable dkim key or certificate would make every send fail, so it is not saved
+    if let Some(problem) = dkim::dkim_problem(config.dkim())
+        .or_else(|| smime::smime_problem(config.smime()))
+        .or_else(|| file_list_utils::post_send_problem(&config))
+    {
+        return render_settings_config(&config, Some(problem)).into_string();
+    }
+
+    config.save_config();
 
     close_settings_config()
 }
@@ -220,8 +908,16 @@ pub fn save_and_close_settings_config(app: tauri::AppHandle) -> String {
 #[tauri::command]
 pub fn discard_and_close_settings_config(app: tauri::AppHandle) -> String {
     let app_state = app.state::<AppState>();
+    let mut mail = app_state.mail.lock().unwrap();
+    let mut config = app_state.config.lock().unwrap();
+    let saved_config = Config::load_config();
+
+    //picked profile was already moved when a profile was removed
+    if config.sender_profiles() != saved_config.sender_profiles() {
+        mail.set_profile(None);
+    }
 
-    *app_state.config.lock().unwrap() = Config::load_config();
+    *config = saved_config;
 
     close_settings_config()
 }
@@ -2,6 +2,7 @@ use maud::{html, Markup};
 use tauri::Manager;
 
 use crate::AppState;
+use crate::backend::locale::tr;
 use crate::backend::mail_sender;
 //---------------------------
 
@@ -16,17 +17,17 @@ pub fn open_feedback() -> String {
                 hx-target="#overlay-feedback"
                 hx-swap="outerHTML"
                 {("X")}
-                h1.overlay-title{("hlášení chyb a nápady na vylepšení")}
+                h1.overlay-title{(tr("feedback"))}
                 textarea.feedback-input
                 name="text"
-                placeholder="Zadejte prosím zprávu pro vývojáře"
+                placeholder=(tr("feedback-placeholder"))
                 {}
                 button.feedback-send-button.save
                 hx-post="command:send_feedback"
                 hx-trigger="click"
                 hx-include="[name='text']"
                 hx-swap="outerHTML"
-                {("odeslat")}
+                {(tr("send"))}
             }
         }
     };
@@ -50,14 +51,14 @@ pub fn send_feedback(text: String, app: tauri::AppHandle) -> String {
 
     if mail_sender::MailSender::send_feedback(text, config).is_ok() {
         let markup: Markup = html! {
-            h1.feedback-send-message{("Zpětná vazba byla odeslána, děkujeme!")}
+            h1.feedback-send-message{(tr("feedback-sent"))}
         };
         return markup.into_string();
     } else {
         let markup: Markup = html! {
-            h1.feedback-send-message{"Nepodařilo se odeslat zpětnou vazbu."
+            h1.feedback-send-message{(tr("feedback-failed"))
             br;
-            "Kontaktujte prosím administrátora!"}
+            (tr("feedback-contact-admin"))}
         };
         return markup.into_string();
     }
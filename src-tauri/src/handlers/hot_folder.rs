@@ -0,0 +1,86 @@
+use maud::{html, Markup};
+use tauri::Manager;
+use tauri_plugin_dialog::FilePath;
+
+use std::path::Path;
+
+use crate::AppState;
+use crate::backend::locale::tr;
+use crate::backend::mail_sender::SkippedFiles;
+use crate::backend::tutorial::TourStep;
+use crate::handlers::mail_send::render_file_section;
+//---------------------------
+
+#[tauri::command]
+pub fn load_new_reports(app: tauri::AppHandle) -> String {
+    render_report_cards(&app).into_string()
+}
+
+#[tauri::command]
+pub fn attach_report(app: tauri::AppHandle, path: String) -> String {
+    let app_state = app.state::<AppState>();
+
+    let report = app_state.hot_folder.lock().unwrap().report(Path::new(&path));
+
+    let config = app_state.config.lock().unwrap().clone();
+    let mut mail = app_state.mail.lock().unwrap();
+
+    //report removed from watched directory since the card was shown can't be attached
+    let skipped = match report {
+        Some(report) => {
+            let skipped = mail.add_file(vec![FilePath::Path(report.path)]);
+            mail.detect_vehicle_info(&config);
+
+            app_state.tutorial.lock().unwrap().complete(TourStep::FilePicker);
+
+            skipped
+        }
+        None => SkippedFiles {
+            unreadable: vec![Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or(path)],
+            ..SkippedFiles::default()
+        },
+    };
+
+    let markup: Markup = html! {
+        (render_file_section(&mail, &skipped))
+        div
+        hx-trigger="load delay:1ms"
+        hx-post="command:load_new_reports"
+        hx-target="#report-cards"
+        hx-swap="innerHTML"
+        {}
+    };
+
+    markup.into_string()
+}
+
+fn render_report_cards(app: &tauri::AppHandle) -> Markup {
+    let app_state = app.state::<AppState>();
+    let config = app_state.config.lock().unwrap().clone();
+    let mail = app_state.mail.lock().unwrap();
+    let mut hot_folder = app_state.hot_folder.lock().unwrap();
+
+    let reports = hot_folder.scan(&config, mail.files());
+
+    html! {
+        @for report in reports {
+            div.report-card {
+                div.file-info {
+                    h2.report-card-title{(tr("report-new"))}
+                    p.file-name{(report.file_name())}
+                    p.file-details{(report.modified_text())}
+                }
+                button.report-card-button.save
+                hx-post="command:attach_report"
+                hx-trigger="click"
+                hx-target="#file-section"
+                hx-swap="outerHTML"
+                hx-vals=(serde_json::json!({ "path": report.path }).to_string())
+                {(tr("report-attach"))}
+            }
+        }
+    }
+}
@@ -3,8 +3,130 @@ use tauri::Manager;
 use tauri_plugin_dialog::DialogExt;
 
 use crate::AppState;
+use crate::backend::config::Config;
+use crate::backend::imap_client::ImapError;
+use crate::backend::locale::{tr, tr_args};
+use crate::backend::mail_list_utils::{Person, MECHANIC_IDS, TECHNIC_IDS};
+use crate::backend::mail_sender::{MailSender, SkippedFiles, ValidationProblem};
+use crate::backend::outbox::Outbox;
+use crate::backend::thumbnail;
+use crate::backend::tutorial::TourStep;
+use crate::backend::vehicle_info::VehicleInfo;
 //---------------------------
 
+#[tauri::command]
+pub fn open_send_confirmation(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+    let mail = app_state.mail.lock().unwrap();
+    let other_mail_list = app_state.other_mail_list.lock().unwrap();
+    let config = app_state.config.lock().unwrap();
+    let profile = config.profile(mail.profile_id(&config));
+
+    let markup: Markup = html! {
+        div .overlay #send-confirmation {
+            div .overlay-window {
+                button.close-button
+                hx-post="command:close_send_confirmation"
+                hx-trigger="click"
+                hx-target="#send-confirmation"
+                hx-swap="outerHTML"
+                {("X")}
+                h1.overlay-title{(tr("send-confirm-title"))}
+                @if let Some(author) = mail.author() {
+                    h2.confirmation-author{(tr_args("send-confirm-author", &[("name", &author.name)]))}
+                }
+                @if config.profile_count() > 1 {
+                    h2.confirmation-author{
+                        (tr_args("send-confirm-profile", &[("label", &profile.label), ("mail", &profile.sender_mail)]))
+                    }
+                }
+                (render_receipts(&mail, &config))
+                div.confirmation-section {
+                    div.confirmation-recipients {
+                        @for recipient in mail.people() {
+                            h2.confirmation-row{(recipient.name)}
+                        }
+                        @for person in other_mail_list.export_other_mail_list() {
+                            h2.confirmation-row{(person.mail)}
+                        }
+                    }
+                    div.confirmation-files {
+                        @for file in mail.files() {
+                            div.confirmation-file {
+                                @if let Some(thumbnail) = &file.thumbnail {
+                                    img.confirmation-thumbnail
+                                    src=(thumbnail)
+                                    alt=(file.file_name())
+                                    {}
+                                }
+                                p.confirmation-file-name{(file.file_name())}
+                            }
+                        }
+                    }
+                }
+                button.feedback-send-button.save
+                hx-post="command:send"
+                hx-trigger="click"
+                hx-target="#truck"
+                hx-swap="outerHTML"
+                {(tr("send"))}
+            }
+        }
+    };
+
+    markup.into_string()
+}
+
+//requests from sender profile can be changed just for this mail
+fn render_receipts(mail: &MailSender, config: &Config) -> Markup {
+    html! {
+        div.confirmation-receipts #confirmation-receipts {
+            @for (command, title, requested) in [
+                ("toggle_delivery_status", tr("receipt-delivery-status"), mail.delivery_status(config)),
+                ("toggle_read_receipt", tr("receipt-read"), mail.read_receipt(config)),
+            ] {
+                button.merge-button.clicked[requested]
+                hx-post={"command:" (command)}
+                hx-trigger="click"
+                hx-target="#confirmation-receipts"
+                hx-swap="outerHTML"
+                {(title) ": " @if requested {(tr("yes"))} @else {(tr("no"))}}
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub fn toggle_delivery_status(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+    let mut mail = app_state.mail.lock().unwrap();
+    let config = app_state.config.lock().unwrap();
+
+    mail.toggle_delivery_status(&config);
+
+    render_receipts(&mail, &config).into_string()
+}
+
+#[tauri::command]
+pub fn toggle_read_receipt(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+    let mut mail = app_state.mail.lock().unwrap();
+    let config = app_state.config.lock().unwrap();
+
+    mail.toggle_read_receipt(&config);
+
+    render_receipts(&mail, &config).into_string()
+}
+
+#[tauri::command]
+pub fn close_send_confirmation() -> String {
+    let markup: Markup = html! {
+        div #send-confirmation-placeholder {}
+    };
+
+    markup.into_string()
+}
+
 #[tauri::command]
 pub fn send(app: tauri::AppHandle) -> String {
     let app_state = app.state::<AppState>();
@@ -12,35 +134,245 @@ pub fn send(app: tauri::AppHandle) -> String {
     let mut other_mail_list = app_state.other_mail_list.lock().unwrap();
     let config = app_state.config.lock().unwrap().clone();
 
-    let file_valid = mail.file_is_valid();
-    let mail_list_not_empty = mail.person_list_is_valid() && other_mail_list.is_empty();
-    let other_mail_list_valid = other_mail_list.is_valid();
+    let other_people = other_mail_list.export_other_mail_list();
 
     //valid check
-    if !(file_valid && ((mail_list_not_empty) || other_mail_list_valid)) {
-        return html!{
-            input.truck
+    let problems = mail.validate(&other_people, &config);
+
+    if !problems.is_empty() {
+        let markup: Markup = html! {
+            (render_send_result(false))
+            (render_validation_warning(&problems))
+        };
+
+        return markup.into_string();
+    }
+
+    //files and recipients stay selected, so sending can be tried again
+    let (history_entry, message) = match mail.send(other_people.clone(), config.clone()) {
+        Ok(sent) => sent,
+        Err(error) => {
+            let markup: Markup = html! {
+                (render_send_result(false))
+                (render_send_error(&error))
+            };
+
+            return markup.into_string();
+        }
+    };
+
+    //copy is stored by store_sent_copies after this response, network doesn't hold the locks
+    if config.imap().saves_sent_copy() {
+        app_state
+            .outbox
+            .lock()
+            .unwrap()
+            .add_copy(&history_entry.message_id, &message.formatted());
+    }
+
+    let mut address_suggestions = app_state.address_suggestions.lock().unwrap();
+    address_suggestions.record(&other_people);
+    address_suggestions.save_suggestions();
+    drop(address_suggestions);
+
+    app_state
+        .send_history
+        .lock()
+        .unwrap()
+        .add_entry(history_entry);
+
+    app_state.tutorial.lock().unwrap().complete(TourStep::Send);
+
+    if config.keep_recipients() {
+        mail.clear_files();
+    } else {
+        mail.clear();
+        other_mail_list.clear();
+    }
+
+    let markup: Markup = html! {
+        (render_send_result(true))
+        @if config.imap().saves_sent_copy() {
+            div
+            hx-trigger="load delay:1ms"
+            hx-post="command:store_sent_copies"
+            hx-swap="outerHTML"
+            {}
+        }
+        div
+        hx-trigger="load delay:1ms"
+        hx-post="command:load_files"
+        hx-target="#file-section"
+        hx-swap="outerHTML"
+        {}
+        div
+        hx-trigger="load delay:1ms"
+        hx-post="command:load_mechanics"
+        hx-target=".mechanic-buttons"
+        hx-swap="innerHTML"
+        {}
+        div
+        hx-trigger="load delay:1ms"
+        hx-post="command:load_technics"
+        hx-target=".right-buttons"
+        hx-swap="innerHTML"
+        {}
+    };
+
+    markup.into_string()
+}
+
+fn render_validation_warning(problems: &[ValidationProblem]) -> Markup {
+    html! {
+        div .overlay .most-top #send-validation {
+            div .overlay-window {
+                button.close-button
+                hx-post="command:close_send_validation"
+                hx-trigger="click"
+                hx-target="#send-validation"
+                hx-swap="outerHTML"
+                {("X")}
+                h1.overlay-title{(tr("validation-title"))}
+                div.mail-warning-rows-section {
+                    @for problem in problems {
+                        @match problem {
+                            ValidationProblem::NoFile => {
+                                h2.mail-warning-row{(tr("validation-no-file"))}
+                            }
+                            ValidationProblem::NoRecipients => {
+                                h2.mail-warning-row{(tr("validation-no-recipients"))}
+                            }
+                            ValidationProblem::InvalidAddress { mail, reason } => {
+                                h2.mail-warning-row{(mail) " - " (tr(reason))}
+                            }
+                            ValidationProblem::MissingCertificate { mail, reason } => {
+                                h2.mail-warning-row{(mail) " - " (tr(reason))}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub fn close_send_validation() -> String {
+    let markup: Markup = html! {
+        div #send-validation-placeholder {}
+    };
+
+    markup.into_string()
+}
+
+fn render_send_error(error: &anyhow::Error) -> Markup {
+    html! {
+        div .overlay .most-top #send-error {
+            div .overlay-window {
+                button.close-button
+                hx-post="command:close_send_error"
+                hx-trigger="click"
+                hx-target="#send-error"
+                hx-swap="outerHTML"
+                {("X")}
+                h1.overlay-title{(tr("send-error-title"))}
+                div.mail-warning-rows-section {
+                    h2.mail-warning-row{(error)}
+                    h2.mail-warning-row{(tr("send-error-retry"))}
+                }
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub fn close_send_error() -> String {
+    let markup: Markup = html! {
+        div #send-error-placeholder {}
+    };
+
+    markup.into_string()
+}
+
+//mail is already sent, failed copy is only reported and retried later from outbox
+#[tauri::command]
+pub async fn store_sent_copies(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+    let settings = app_state.config.lock().unwrap().imap().clone();
+
+    match Outbox::flush(&app_state.outbox, &settings) {
+        Ok(()) => String::new(),
+        Err(error) => render_sent_copy_warning(&error).into_string(),
+    }
+}
+
+fn render_sent_copy_warning(error: &ImapError) -> Markup {
+    html! {
+        div .overlay .most-top #sent-copy-warning {
+            div .overlay-window {
+                button.close-button
+                hx-post="command:close_sent_copy_warning"
+                hx-trigger="click"
+                hx-target="#sent-copy-warning"
+                hx-swap="outerHTML"
+                {("X")}
+                h1.overlay-title{(tr("sent-copy-title"))}
+                div.mail-warning-rows-section {
+                    h2.mail-warning-row{
+                        @match error {
+                            ImapError::NoConnection => {(tr("imap-no-connection"))}
+                            ImapError::Rejected(reason) => {(tr_args("imap-rejected", &[("reason", reason)]))}
+                            ImapError::InvalidResponse => {(tr("imap-invalid-response"))}
+                        }
+                    }
+                    h2.mail-warning-row{(tr("sent-copy-retry"))}
+                }
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub fn close_sent_copy_warning() -> String {
+    let markup: Markup = html! {
+        div #sent-copy-warning-placeholder {}
+    };
+
+    markup.into_string()
+}
+
+//truck drives away after successful send, confirmation overlay is closed
+fn render_send_result(sent: bool) -> Markup {
+    html! {
+        @if sent {
+            input.truck.drive-animation #truck
             type="image"
             src="src/assets/send_truck.svg"
             alt="truck-icon"
             hx-trigger="click"
-            hx-post="command:send"
+            hx-post="command:open_send_confirmation"
+            hx-target="#send-confirmation-placeholder"
+            hx-swap="outerHTML"
             {}
-        }.into_string();
-    }
-
-    mail.send(other_mail_list.export_other_mail_list(), config).unwrap();
-
-    html!{
-        input.truck.drive-animation
-        type="image"
-        src="src/assets/send_truck.svg"
-        alt="truck-icon"
-        hx-trigger="click"
-        hx-post="command:send"
+        }
+        @else {
+            input.truck #truck
+            type="image"
+            src="src/assets/send_truck.svg"
+            alt="truck-icon"
+            hx-trigger="click"
+            hx-post="command:open_send_confirmation"
+            hx-target="#send-confirmation-placeholder"
+            hx-swap="outerHTML"
+            {}
+        }
+        div
+        hx-trigger="load delay:1ms"
+        hx-post="command:close_send_confirmation"
+        hx-target="#send-confirmation"
         hx-swap="outerHTML"
         {}
-    }.into_string()
+    }
 }
 
 #[tauri::command]
@@ -48,16 +380,27 @@ pub fn load_mechanics(app: tauri::AppHandle) -> String {
     let app_state = app.state::<AppState>();
 
     let mail_list = app_state.mail_list.lock().unwrap();
+    let mail = app_state.mail.lock().unwrap();
 
     let markup: Markup = html! {
         @for i in 0..24 {
             @if let Some(mechanic) = mail_list.load_person(i){
-                button.middle-button
-                hx-trigger="click"
-                hx-post="command:add_person"
-                hx-swap="outerHTML"
-                hx-vals={(format!(r#""id": {i}"#))}
-                {(mechanic.name)}
+                @if mail.has_person(&mechanic) {
+                    button.middle-button.clicked
+                    hx-trigger="click"
+                    hx-post="command:remove_person"
+                    hx-swap="outerHTML"
+                    hx-vals={(format!(r#""id": {i}"#))}
+                    {(mechanic.name)}
+                }
+                @else {
+                    button.middle-button
+                    hx-trigger="click"
+                    hx-post="command:add_person"
+                    hx-swap="outerHTML"
+                    hx-vals={(format!(r#""id": {i}"#))}
+                    {(mechanic.name)}
+                }
             }
             @else{
                 button.middle-button.placeholder{}
@@ -73,27 +416,178 @@ pub fn load_technics(app: tauri::AppHandle) -> String {
     let app_state = app.state::<AppState>();
 
     let mail_list = app_state.mail_list.lock().unwrap();
+    let mail = app_state.mail.lock().unwrap();
+    let has_last_recipients = app_state
+        .send_history
+        .lock()
+        .unwrap()
+        .last_entry()
+        .is_some();
 
     let markup: Markup = html! {
         @for i in 24..29 {
             @if let Some(technic) = mail_list.load_person(i){
-                button.middle-button
-                hx-trigger="click"
-                hx-post="command:add_person"
-                hx-swap="outerHTML"
-                hx-vals={(format!(r#""id": {i}"#))}
-                {(technic.name)}
+                @if mail.has_person(&technic) {
+                    button.middle-button.clicked
+                    hx-trigger="click"
+                    hx-post="command:remove_person"
+                    hx-swap="outerHTML"
+                    hx-vals={(format!(r#""id": {i}"#))}
+                    {(technic.name)}
+                }
+                @else {
+                    button.middle-button
+                    hx-trigger="click"
+                    hx-post="command:add_person"
+                    hx-swap="outerHTML"
+                    hx-vals={(format!(r#""id": {i}"#))}
+                    {(technic.name)}
+                }
             }
             @else{
                 button.middle-button.placeholder{}
             }
         }
-        button.middle-button
+        button.middle-button.other-button
         hx-post="command:open_other"
         hx-trigger="click"
         hx-target="#overlay-other-placeholder"
         hx-swap="outerHTML"
-        {("ostatní...")}
+        {(tr("other-recipients"))}
+        @if has_last_recipients {
+            button.middle-button
+            hx-post="command:restore_last_recipients"
+            hx-trigger="click"
+            hx-target="closest .center-buttons"
+            hx-swap="innerHTML"
+            {(tr("last-recipients"))}
+        }
+    };
+
+    markup.into_string()
+}
+
+#[tauri::command]
+pub fn load_author_select(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+
+    let mail_list = app_state.mail_list.lock().unwrap();
+    let mail = app_state.mail.lock().unwrap();
+
+    let markup: Markup = html! {
+        select.author-select
+        hx-post="command:select_author"
+        hx-trigger="change"
+        name="text"
+        {
+            option value="" selected[mail.author().is_none()] {(tr("author-select"))}
+            @for i in MECHANIC_IDS.start..TECHNIC_IDS.end {
+                @if let Some(person) = mail_list.load_person(i) {
+                    option
+                    value=(i)
+                    selected[mail.author().is_some_and(|author| *author == person)]
+                    {(person.name)}
+                }
+            }
+        }
+    };
+
+    markup.into_string()
+}
+
+#[tauri::command]
+pub fn select_author(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    let author = text
+        .parse()
+        .ok()
+        .and_then(|id| app_state.mail_list.lock().unwrap().load_person(id));
+
+    app_state.mail.lock().unwrap().set_author(author);
+}
+
+//selector is shown only when admin enabled it and there is something to choose from
+#[tauri::command]
+pub fn load_profile_select(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+
+    let config = app_state.config.lock().unwrap();
+    let mail = app_state.mail.lock().unwrap();
+
+    let markup: Markup = html! {
+        @if config.profile_selector() && config.profile_count() > 1 {
+            select.author-select
+            hx-post="command:select_profile"
+            hx-trigger="change"
+            name="text"
+            {
+                @for id in 0..config.profile_count() {
+                    option
+                    value=(id)
+                    selected[id == mail.profile_id(&config)]
+                    {(config.profile(id).label)}
+                }
+            }
+        }
+    };
+
+    markup.into_string()
+}
+
+#[tauri::command]
+pub fn select_profile(app: tauri::AppHandle, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state.mail.lock().unwrap().set_profile(text.parse().ok());
+}
+
+//selects the same roster people and ad-hoc addresses as in the last sent mail
+#[tauri::command]
+pub fn restore_last_recipients(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+
+    let last_entry = app_state.send_history.lock().unwrap().last_entry().cloned();
+
+    if let Some(last_entry) = last_entry {
+        let mail_list = app_state.mail_list.lock().unwrap();
+        let mut mail = app_state.mail.lock().unwrap();
+        let mut other_mail_list = app_state.other_mail_list.lock().unwrap();
+
+        let roster: Vec<Person> = (MECHANIC_IDS.start..TECHNIC_IDS.end)
+            .filter_map(|id| mail_list.load_person(id))
+            .collect();
+
+        let mut roster_recipients = last_entry.roster_recipients;
+        roster_recipients.retain(|person| !mail.has_person(person));
+
+        for person in roster_recipients {
+            mail.add_person(person);
+        }
+
+        //removed roster people are not selected again
+        mail.retain_people(&roster);
+
+        other_mail_list.clear();
+
+        for person in last_entry.other_recipients {
+            other_mail_list.add_person(person);
+        }
+
+        app_state.tutorial.lock().unwrap().complete(TourStep::Recipients);
+    }
+
+    let markup: Markup = html! {
+        div.mechanic-buttons
+        hx-trigger="load delay:1ms"
+        hx-swap="innerHTML"
+        hx-post="command:load_mechanics"
+        {}
+        div.right-buttons
+        hx-trigger="load delay:1ms"
+        hx-swap="innerHTML"
+        hx-post="command:load_technics"
+        {}
     };
 
     markup.into_string()
@@ -107,6 +601,7 @@ pub fn add_person(id: String, app: tauri::AppHandle) -> String {
     let person = app_state.mail_list.lock().unwrap().load_person(id).unwrap();
 
     app_state.mail.lock().unwrap().add_person(person.clone());
+    app_state.tutorial.lock().unwrap().complete(TourStep::Recipients);
 
     let markup: Markup = html! {
         button.middle-button.clicked
@@ -143,28 +638,191 @@ pub fn remove_person(id: String, app: tauri::AppHandle) -> String {
 
 
 #[tauri::command]
-pub fn pick_file(app: tauri::AppHandle) -> String {
-    app.dialog().file().pick_files(move |file_path| {
-        let app_state = app.state::<AppState>();
+pub async fn pick_file(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
 
-        if file_path.is_some() {
-            app_state
-                .mail
-                .lock()
-                .unwrap()
-                .add_file(file_path.unwrap())
-                .unwrap();
-        }
-    });
+    let file_path = app.dialog().file().blocking_pick_files();
 
-    let markup: Markup = html! {
-        button.choosen-file-picker
-        hx-trigger="click"
-        hx-post="command:pick_file"
-        hx-swap="outerHTML"
-        {"soubor(y) vybrán(y)"}
+    let config = app_state.config.lock().unwrap().clone();
+    let mut mail = app_state.mail.lock().unwrap();
+
+    let skipped = match file_path {
+        Some(file_path) => {
+            app_state.tutorial.lock().unwrap().complete(TourStep::FilePicker);
+            mail.add_file(file_path)
+        }
+        None => SkippedFiles::default(),
     };
 
-    markup.into_string()
+    mail.detect_vehicle_info(&config);
+
+    render_file_section(&mail, &skipped).into_string()
 }
 
+#[tauri::command]
+pub fn remove_file(app: tauri::AppHandle, index: String) -> String {
+    let app_state = app.state::<AppState>();
+    let index: usize = index.parse().unwrap();
+
+    let config = app_state.config.lock().unwrap().clone();
+    let mut mail = app_state.mail.lock().unwrap();
+
+    mail.remove_file(index, &config);
+
+    render_file_section(&mail, &SkippedFiles::default()).into_string()
+}
+
+#[tauri::command]
+pub fn move_file_up(app: tauri::AppHandle, index: String) -> String {
+    let app_state = app.state::<AppState>();
+    let index: usize = index.parse().unwrap();
+
+    let mut mail = app_state.mail.lock().unwrap();
+
+    mail.move_file_up(index);
+
+    render_file_section(&mail, &SkippedFiles::default()).into_string()
+}
+
+#[tauri::command]
+pub fn toggle_merge_pdfs(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+
+    let mut mail = app_state.mail.lock().unwrap();
+
+    mail.toggle_merge_pdfs();
+
+    render_file_section(&mail, &SkippedFiles::default()).into_string()
+}
+
+#[tauri::command]
+pub fn load_files(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+
+    let mail = app_state.mail.lock().unwrap();
+
+    render_file_section(&mail, &SkippedFiles::default()).into_string()
+}
+
+#[tauri::command]
+pub fn edit_vehicle_info(app: tauri::AppHandle, field: String, text: String) {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .mail
+        .lock()
+        .unwrap()
+        .edit_vehicle_info(&field, text);
+}
+
+pub fn render_file_section(mail: &MailSender, skipped: &SkippedFiles) -> Markup {
+    html! {
+        div.file-section #file-section {
+            @if !mail.files().is_empty() || skipped != &SkippedFiles::default() {
+                div.file-list {
+                    @for (index, file) in mail.files().iter().enumerate() {
+                        div.file-row {
+                            @if let Some(thumbnail) = &file.thumbnail {
+                                img.file-thumbnail
+                                src=(thumbnail)
+                                alt=(file.file_name())
+                                {}
+                            }
+                            div.file-info {
+                                h2.file-name{(file.file_name())}
+                                p.file-details{(file.size_text()) " | " (file.modified_text())}
+                            }
+                            @if index > 0 {
+                                button.move-button
+                                hx-post="command:move_file_up"
+                                hx-trigger="click"
+                                hx-target="#file-section"
+                                hx-swap="outerHTML"
+                                hx-vals={(format!(r#""index": {index}"#))}
+                                {("▲")}
+                            }
+                            button.remove-button
+                            hx-post="command:remove_file"
+                            hx-trigger="click"
+                            hx-target="#file-section"
+                            hx-swap="outerHTML"
+                            hx-vals={(format!(r#""index": {index}"#))}
+                            {(tr("remove"))}
+                        }
+                    }
+                    @for name in &skipped.duplicates {
+                        p.file-duplicate-warning{(tr_args("file-already-selected", &[("name", name)]))}
+                    }
+                    @for name in &skipped.unreadable {
+                        p.file-duplicate-warning{(tr_args("file-unreadable", &[("name", name)]))}
+                    }
+                    @if mail.files().iter().any(|file| file.is_pdf()) && !thumbnail::renderer_available() {
+                        p.file-duplicate-warning{(tr("thumbnails-unavailable"))}
+                    }
+                    @if mail.can_merge_pdfs() {
+                        @if mail.merge_pdfs() {
+                            button.merge-button.clicked
+                            hx-post="command:toggle_merge_pdfs"
+                            hx-trigger="click"
+                            hx-target="#file-section"
+                            hx-swap="outerHTML"
+                            {(tr("merge-pdfs-on"))}
+                        }
+                        @else {
+                            button.merge-button
+                            hx-post="command:toggle_merge_pdfs"
+                            hx-trigger="click"
+                            hx-target="#file-section"
+                            hx-swap="outerHTML"
+                            {(tr("merge-pdfs-off"))}
+                        }
+                    }
+                }
+            }
+            @if !mail.files().is_empty() {
+                (render_vehicle_info(mail.vehicle_info()))
+            }
+            @if mail.files().is_empty() {
+                button.file-picker
+                hx-trigger="click"
+                hx-post="command:pick_file"
+                hx-target="#file-section"
+                hx-swap="outerHTML"
+                {(tr("pick-files"))}
+            }
+            @else {
+                button.file-picker.add-files
+                hx-trigger="click"
+                hx-post="command:pick_file"
+                hx-target="#file-section"
+                hx-swap="outerHTML"
+                {(tr("add-files"))}
+            }
+        }
+    }
+}
+
+fn render_vehicle_info(vehicle: &VehicleInfo) -> Markup {
+    html! {
+        div.vehicle-info {
+            @for (field, title, value) in [
+                ("vin", tr("vehicle-vin"), &vehicle.vin),
+                ("plate", tr("vehicle-plate"), &vehicle.plate),
+                ("job_number", tr("vehicle-job-number"), &vehicle.job_number),
+            ] {
+                div.vehicle-info-row {
+                    p.vehicle-info-title{(title)}
+                    input.vehicle-info-input
+                    type="text"
+                    hx-post="command:edit_vehicle_info"
+                    name="text"
+                    hx-trigger="change"
+                    hx-vals={(format!(r#""field": "{field}""#))}
+                    placeholder=(tr("vehicle-not-found"))
+                    value=(value)
+                    {}
+                }
+            }
+        }
+    }
+}
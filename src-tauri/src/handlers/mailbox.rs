@@ -0,0 +1,116 @@
+use chrono::{Duration, Local};
+use maud::{html, Markup};
+use tauri::Manager;
+
+use crate::backend::locale::tr;
+use crate::backend::mailbox_poller::MailboxPoller;
+use crate::backend::outbox::Outbox;
+use crate::backend::send_history::MailStatus;
+use crate::AppState;
+//---------------------------
+
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(120);
+
+//bounces and receipts for older mail are not expected
+const PENDING_DAYS: i64 = 14;
+
+//mailbox is checked on background thread, network never blocks ui
+pub fn start_mailbox_poller(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut poller = MailboxPoller::default();
+
+        loop {
+            retry_sent_copies(&app);
+            poll_mailbox(&app, &mut poller);
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+//copies which imap server didn't accept after send
+fn retry_sent_copies(app: &tauri::AppHandle) {
+    let app_state = app.state::<AppState>();
+
+    let settings = app_state.config.lock().unwrap().imap().clone();
+    if !settings.saves_sent_copy() {
+        return;
+    }
+
+    Outbox::flush(&app_state.outbox, &settings).ok();
+}
+
+fn poll_mailbox(app: &tauri::AppHandle, poller: &mut MailboxPoller) {
+    let app_state = app.state::<AppState>();
+
+    let settings = app_state.config.lock().unwrap().imap().clone();
+    if !settings.is_enabled() {
+        return;
+    }
+
+    let message_ids = app_state
+        .send_history
+        .lock()
+        .unwrap()
+        .pending_message_ids(PENDING_DAYS);
+    if message_ids.is_empty() {
+        return;
+    }
+
+    let since = (Local::now() - Duration::days(PENDING_DAYS)).date_naive();
+
+    //unreachable server is tried again next time
+    let Ok(events) = poller.poll(&settings, &message_ids, since) else {
+        return;
+    };
+
+    let mut send_history = app_state.send_history.lock().unwrap();
+
+    for (message_id, event) in events {
+        if let Some(entry) = send_history.apply_event(&message_id, event) {
+            if entry.status == MailStatus::Failed {
+                app_state.mailbox_alerts.lock().unwrap().push(entry.clone());
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub fn load_mailbox_alerts(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+    let alerts = app_state.mailbox_alerts.lock().unwrap();
+
+    if alerts.is_empty() {
+        return String::new();
+    }
+
+    let markup: Markup = html! {
+        div .overlay .most-top {
+            div .overlay-window {
+                button.close-button
+                hx-post="command:dismiss_mailbox_alerts"
+                hx-trigger="click"
+                hx-target="#mailbox-alerts"
+                hx-swap="innerHTML"
+                {("X")}
+                h1.overlay-title{(tr("mailbox-failed-title"))}
+                div.mail-warning-rows-section {
+                    @for entry in alerts.iter() {
+                        h2.mail-warning-row{
+                            (entry.time.format("%d.%m.%Y %H:%M")) " - " (entry.recipients.join(", "))
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    markup.into_string()
+}
+
+#[tauri::command]
+pub fn dismiss_mailbox_alerts(app: tauri::AppHandle) -> String {
+    let app_state = app.state::<AppState>();
+    app_state.mailbox_alerts.lock().unwrap().clear();
+
+    String::new()
+}
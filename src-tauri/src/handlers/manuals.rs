@@ -1,4 +1,7 @@
-use maud::{html, Markup};
+use maud::{html, Markup, PreEscaped};
+
+use crate::backend::locale::{current_locale, tr};
+use crate::backend::manual_pages::{render_page, ManualPage};
 
 //---------------------------
 
@@ -13,22 +16,15 @@ pub fn open_manual() -> String {
                 hx-target="#overlay-manual"
                 hx-swap="outerHTML"
                 {("X")}
-                h1.overlay-title{("Návod k použití")}
-                ol.manual-text{
-                    li{("Vyberte přjemce (možné vybrat více)")}
-                    ol{
-                        li{("Kliknutím na jméno ve výběru")}
-                        li{("Kliknutím na \"Ostatní...\"")}
-                        ol{
-                            li{("Kliknutím na \"přidat další E-mail\"")}
-                            li{("Zadáním E-mailu do nově přidaného pole")}
-                            li{("V případě potřeby lze pole smazat tlačítkem \"smazat\"")}
-                            li{("Po zadání všech E-mailů můžete okno standardně zavřít křížkem")}
-                        }
-                    }
-                    li{("Vyberte soubor k odeslání (možné vybrat více)")}
-                    li{("Klikněte na odeslat")}
-                }
+                h1.overlay-title{(tr("manual-title"))}
+                div.manual-text{(PreEscaped(render_page(ManualPage::Main, current_locale())))}
+                button.tutorial-start-button
+                hx-post="command:restart_tutorial"
+                hx-trigger="click"
+                hx-target="#overlay-manual"
+                hx-swap="outerHTML"
+                hx-vals=r#""tour": "main""#
+                {(tr("tutorial-start"))}
             }
         }
 
@@ -57,20 +53,15 @@ pub fn open_settings_manual() -> String {
                     hx-target="#overlay-manual"
                     hx-swap="outerHTML"
                     {("X")}
-                    h1.overlay-title{("Návod k použití nastavení")}
-                    ol.manual-text{
-                        li{("Vyberte osobu ke změně nebo smazání údajů")}
-                        ol{
-                            li{("Přidání osoby - Přidejte jméno a E-mail vybrané osoby")}
-                            li{("Úprava osoby - Upravte jméno nebo E-mail vybrané osoby")}
-                            li{("Smazání osoby - Smažte jméno osoby")}
-                        }
-                        li{("Po dokončení změn")}
-                        ol{
-                            li{("Pro uložení změn - klikněte na \"uložit a zavřít\"")}
-                            li{("Pro zrušení všech změn - klikněte na \"zavřít bez uložení\"")}
-                        }
-                    }
+                    h1.overlay-title{(tr("settings-manual-title"))}
+                    div.manual-text{(PreEscaped(render_page(ManualPage::Settings, current_locale())))}
+                    button.tutorial-start-button
+                    hx-post="command:restart_tutorial"
+                    hx-trigger="click"
+                    hx-target="#overlay-manual"
+                    hx-swap="outerHTML"
+                    hx-vals=r#""tour": "settings""#
+                    {(tr("tutorial-start"))}
                 }
             }
     };
@@ -2,12 +2,18 @@ use maud::{html, Markup};
 use tauri::Manager;
 
 use crate::AppState;
+use crate::backend::locale::tr;
+use crate::backend::mail_list_utils::{MailList, Person, MECHANIC_IDS, TECHNIC_IDS};
+use crate::backend::other_mail_utils::{display_text, render_row};
+use crate::backend::tutorial::TourStep;
 //---------------------------
 
 #[tauri::command]
 pub fn open_other(app: tauri::AppHandle) -> String {
     let app_state = app.state::<AppState>();
 
+    app_state.tutorial.lock().unwrap().complete(TourStep::OtherButton);
+
     let markup: Markup = html! {
         div #overlay-other .overlay
         {
@@ -19,7 +25,18 @@ pub fn open_other(app: tauri::AppHandle) -> String {
                 hx-target="#overlay-other"
                 hx-swap="outerHTML"
                 {("X")}
-                h1.overlay-title{("zadejte prosím E-mailové adresy")}
+                h1.overlay-title{(tr("other-title"))}
+                datalist #other-mail-suggestions {
+                    @for address in app_state.address_suggestions.lock().unwrap().suggestions() {
+                        option
+                        value=(display_text(&Person {
+                            name: address.name.clone(),
+                            mail: address.mail.clone(),
+                            certificate: String::new(),
+                        }))
+                        {}
+                    }
+                }
                 div.other-mail-buttons #other-mail-buttons
                 {(app_state.other_mail_list.lock().unwrap().render_input_fields())}
                 div.bottom-button-row{
@@ -28,8 +45,9 @@ pub fn open_other(app: tauri::AppHandle) -> String {
                     hx-trigger="click"
                     hx-target="#other-mail-list-placeholder"
                     hx-swap="outerHTML"
-                    {("přidat další E-mail")}
+                    {(tr("other-add"))}
                 }
+                div #promote-person-placeholder {}
             }
         }
     };
@@ -40,65 +58,44 @@ pub fn open_other(app: tauri::AppHandle) -> String {
 pub fn add_other_mail_row(app: tauri::AppHandle) -> String {
     let app_state = app.state::<AppState>();
 
-    let index = app_state.other_mail_list.lock().unwrap().size();
+    let mut other_mail_list = app_state.other_mail_list.lock().unwrap();
+
+    let id = other_mail_list.add_row();
 
     let markup: Markup = html! {
-        div.other-mail-button-row{
-            input.other-mail-input-field
-            type="text"
-            hx-post="command:edit_mail"
-            name="text"
-            hx-trigger="change"
-            placeholder="Zadejte prosím E-mail"
-            hx-vals={(format!(r#""index": {index}"#))}
-            {}
-            button.remove-button
-            hx-post="command:remove_other_row"
-            hx-trigger="click"
-            hx-target="#other-mail-buttons"
-            hx-swap="innerHTML"
-            hx-vals={(format!(r#""index": {index}"#))}
-            {("odstranit")}
+        @if let Some(person) = other_mail_list.person(id) {
+            (render_row(id, &person))
         }
 
         div #other-mail-list-placeholder {}
     };
 
-    app_state.other_mail_list.lock().unwrap().increment_size();
-    app_state.other_mail_list.lock().unwrap().add_person();
-
     markup.into_string()
 }
 
 #[tauri::command]
-pub fn edit_mail(app: tauri::AppHandle, index: String, text: String) {
+pub fn edit_mail(app: tauri::AppHandle, id: String, text: String) -> String {
     let app_state = app.state::<AppState>();
 
-    let index: usize = index.parse().unwrap();
+    let id: usize = id.parse().unwrap();
 
-    app_state
-        .other_mail_list
-        .lock()
-        .unwrap()
-        .edit_person(&text, index);
+    let mut other_mail_list = app_state.other_mail_list.lock().unwrap();
+
+    other_mail_list.edit_row(id, &text);
+
+    other_mail_list.render_input_fields().into_string()
 }
 
 #[tauri::command]
-pub fn remove_other_row(app: tauri::AppHandle, index: String) -> String {
+pub fn remove_other_row(app: tauri::AppHandle, id: String) -> String {
     let app_state: tauri::State<'_, AppState> = app.state::<AppState>();
-    let index: usize = index.parse().unwrap();
+    let id: usize = id.parse().unwrap();
 
-    app_state
-        .other_mail_list
-        .lock()
-        .unwrap()
-        .remove_person(index);
+    let mut other_mail_list = app_state.other_mail_list.lock().unwrap();
 
-    let markup: Markup = app_state
-        .other_mail_list
-        .lock()
-        .unwrap()
-        .render_input_fields();
+    other_mail_list.remove_row(id);
+
+    let markup: Markup = other_mail_list.render_input_fields();
 
     markup.into_string()
 }
@@ -115,7 +112,179 @@ pub fn close_other(app: tauri::AppHandle) -> String {
         .other_mail_list
         .lock()
         .unwrap()
-        .remove_empty_persons();
+        .remove_empty_rows();
+
+    app_state.tutorial.lock().unwrap().complete(TourStep::OtherDialog);
 
     markup.into_string()
 }
+
+#[tauri::command]
+pub fn open_promote_person(app: tauri::AppHandle, id: String) -> String {
+    let app_state = app.state::<AppState>();
+    let id: usize = id.parse().unwrap();
+
+    let person = app_state.other_mail_list.lock().unwrap().person(id);
+
+    match person {
+        Some(person) => {
+            render_promote_person(id, &person.name, &person.mail, None).into_string()
+        }
+        None => close_promote_person(),
+    }
+}
+
+#[tauri::command]
+pub fn close_promote_person() -> String {
+    let markup: Markup = html! {
+        div #promote-person-placeholder {}
+    };
+
+    markup.into_string()
+}
+
+//saving to roster needs settings password
+#[tauri::command]
+pub fn promote_person(
+    app: tauri::AppHandle,
+    id: String,
+    name: String,
+    category: String,
+    password: String,
+) -> String {
+    let app_state = app.state::<AppState>();
+    let id: usize = id.parse().unwrap();
+
+    let person = app_state.other_mail_list.lock().unwrap().person(id);
+
+    let Some(person) = person else {
+        return close_promote_person();
+    };
+
+    if !app_state
+        .config
+        .lock()
+        .unwrap()
+        .settings_password_check(&password)
+    {
+        return render_promote_person(id, &name, &person.mail, Some("promote-wrong-password"))
+            .into_string();
+    }
+
+    if name.trim().is_empty() {
+        return render_promote_person(id, &name, &person.mail, Some("promote-missing-name"))
+            .into_string();
+    }
+
+    let ids = match category.as_str() {
+        "technic" => TECHNIC_IDS,
+        _ => MECHANIC_IDS,
+    };
+
+    let mut mail_list = app_state.mail_list.lock().unwrap();
+
+    let new_person = Person {
+        name: name.trim().to_string(),
+        mail: person.mail.clone(),
+        certificate: String::new(),
+    };
+
+    if mail_list.add_person(new_person, ids).is_none() {
+        return render_promote_person(
+            id,
+            &name,
+            &person.mail,
+            Some("promote-category-full"),
+        )
+        .into_string();
+    }
+
+    if mail_list.save_list().is_err() {
+        *mail_list = MailList::load_list();
+
+        return render_promote_person(
+            id,
+            &name,
+            &person.mail,
+            Some("promote-save-failed"),
+        )
+        .into_string();
+    }
+
+    let markup: Markup = html! {
+        div #promote-person-placeholder {}
+        div
+        hx-trigger="load delay:1ms"
+        hx-post="command:load_mechanics"
+        hx-target=".mechanic-buttons"
+        hx-swap="innerHTML"
+        {}
+        div
+        hx-trigger="load delay:1ms"
+        hx-post="command:load_technics"
+        hx-target=".right-buttons"
+        hx-swap="innerHTML"
+        {}
+    };
+
+    markup.into_string()
+}
+
+fn render_promote_person(id: usize, name: &str, mail: &str, error: Option<&str>) -> Markup {
+    html! {
+        div .overlay .most-top #promote-person {
+            div .overlay-window {
+                button.close-button
+                hx-post="command:close_promote_person"
+                hx-trigger="click"
+                hx-target="#promote-person"
+                hx-swap="outerHTML"
+                {("X")}
+                h1.overlay-title{(tr("promote-title"))}
+                div.config-row-section {
+                    div.config-row {
+                        h1.config-row-title{(tr("promote-mail"))}
+                        h1.config-row-title.config-row-value{(mail)}
+                    }
+                    div.config-row {
+                        h1.config-row-title{(tr("promote-name"))}
+                        input.config-row-input-field
+                        type="text"
+                        name="name"
+                        value=(name)
+                        {}
+                    }
+                    div.config-row {
+                        h1.config-row-title{(tr("promote-category"))}
+                        select.config-row-input-field
+                        name="category"
+                        {
+                            option value="mechanic" {(tr("promote-mechanics"))}
+                            option value="technic" {(tr("promote-technics"))}
+                        }
+                    }
+                    div.config-row {
+                        h1.config-row-title{(tr("config-settings-password"))}
+                        input.config-row-input-field
+                        type="password"
+                        name="password"
+                        {}
+                    }
+                    @if let Some(error) = error {
+                        h2.mail-warning-row{(tr(error))}
+                    }
+                }
+                div.bottom-button-row {
+                    button.save-config.save
+                    hx-post="command:promote_person"
+                    hx-trigger="click"
+                    hx-include="#promote-person"
+                    hx-target="#promote-person"
+                    hx-swap="outerHTML"
+                    hx-vals={(format!(r#""id": {id}"#))}
+                    {(tr("save"))}
+                }
+            }
+        }
+    }
+}
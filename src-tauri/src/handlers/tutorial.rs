@@ -0,0 +1,84 @@
+use maud::{html, Markup};
+use tauri::Manager;
+
+use crate::AppState;
+use crate::backend::locale::{tr, tr_args};
+use crate::backend::tutorial::Tour;
+use crate::handlers::manuals::{close_manual, close_settings_manual};
+
+//---------------------------
+
+//reloaded after every other request, so the tour follows what user just did
+#[tauri::command]
+pub fn load_tutorial(app: tauri::AppHandle, tour: String) -> String {
+    let app_state = app.state::<AppState>();
+    let tour = Tour::from_code(&tour);
+
+    let tutorial = app_state.tutorial.lock().unwrap();
+
+    let Some(step) = tutorial.current_step(tour) else {
+        return String::new();
+    };
+
+    let step_number = tutorial.step_number(tour).to_string();
+    let step_count = tour.steps().len().to_string();
+
+    let markup: Markup = html! {
+        style {
+            (format!("{} {{ outline: 5px solid #e40045; outline-offset: 5px; }}", step.target()))
+        }
+        div.tutorial-bubble {
+            p.tutorial-progress{
+                (tr_args("tutorial-progress", &[("step", &step_number), ("count", &step_count)]))
+            }
+            h2.tutorial-text{(tr(step.message_key()))}
+            button.tutorial-skip
+            hx-post="command:skip_tutorial"
+            hx-trigger="click"
+            hx-target="#tutorial"
+            hx-swap="innerHTML"
+            hx-vals={(format!(r#""tour": "{}""#, tour.code()))}
+            {(tr("tutorial-skip"))}
+        }
+    };
+
+    markup.into_string()
+}
+
+#[tauri::command]
+pub fn skip_tutorial(app: tauri::AppHandle, tour: String) -> String {
+    let app_state = app.state::<AppState>();
+
+    app_state
+        .tutorial
+        .lock()
+        .unwrap()
+        .skip(Tour::from_code(&tour));
+
+    String::new()
+}
+
+//started again from manual, manual is closed so the highlighted buttons are visible
+#[tauri::command]
+pub fn restart_tutorial(app: tauri::AppHandle, tour: String) -> String {
+    let app_state = app.state::<AppState>();
+    let tour = Tour::from_code(&tour);
+
+    app_state.tutorial.lock().unwrap().restart(tour);
+
+    match tour {
+        Tour::Main => close_manual(),
+        Tour::Settings => close_settings_manual(),
+    }
+}
+
+pub fn render_tutorial(tour: Tour) -> Markup {
+    html! {
+        div #tutorial
+        hx-trigger="load delay:1ms, htmx:afterRequest[detail.elt.id!='tutorial'] from:document"
+        hx-swap="innerHTML"
+        hx-post="command:load_tutorial"
+        hx-vals={(format!(r#""tour": "{}""#, tour.code()))}
+        {}
+    }
+}
@@ -9,16 +9,39 @@ use tauri::Manager;
 //import backend for AppState
 
 mod backend{
+    pub mod address_suggestions;
     pub mod config;
+    pub mod dkim;
+    pub mod file_list_utils;
+    pub mod hot_folder;
+    pub mod imap_client;
+    pub mod instance_lock;
+    pub mod locale;
     pub mod mail_list_utils;
     pub mod mail_sender;
+    pub mod mailbox_poller;
+    pub mod manual_pages;
+    pub mod name_template;
+    pub mod oauth;
     pub mod other_mail_utils;
+    pub mod outbox;
+    pub mod pdf_merge;
+    pub mod send_history;
+    pub mod smime;
+    pub mod thumbnail;
+    pub mod tutorial;
+    pub mod vehicle_info;
 }
 
+use crate::backend::address_suggestions::AddressSuggestions;
 use crate::backend::config::Config;
+use crate::backend::hot_folder::HotFolder;
 use crate::backend::mail_sender::MailSender;
 use crate::backend::mail_list_utils::MailList;
 use crate::backend::other_mail_utils::OtherMailList;
+use crate::backend::outbox::Outbox;
+use crate::backend::send_history::{HistoryEntry, SendHistory};
+use crate::backend::tutorial::Tutorial;
 
 struct AppState {
     mail: Mutex<MailSender>,
@@ -26,6 +49,12 @@ struct AppState {
     other_mail_list: Mutex<OtherMailList>,
     settings_current_person_id: Mutex<Option<usize>>,
     config: Mutex<Config>,
+    hot_folder: Mutex<HotFolder>,
+    send_history: Mutex<SendHistory>,
+    address_suggestions: Mutex<AddressSuggestions>,
+    mailbox_alerts: Mutex<Vec<HistoryEntry>>,
+    outbox: Mutex<Outbox>,
+    tutorial: Mutex<Tutorial>,
 }
 
 //---------------------------
@@ -33,14 +62,29 @@ struct AppState {
 //import handlers from other files
 
 mod handlers{
+    pub mod address_suggestions;
     pub mod app_settings;
     pub mod config_edit;
     pub mod feedback;
+    pub mod hot_folder;
     pub mod mail_send;
+    pub mod mailbox;
     pub mod manuals;
     pub mod other_mail;
+    pub mod tutorial;
 }
 
+/* 
+address_suggestions:
+    - open_settings_addresses
+    - close_settings_addresses
+    - purge_address
+    - toggle_block_address
+*/
+use crate::handlers::address_suggestions::*;
+
+//---------------------------
+
 /* 
 app_settings:
     - open_settings_password
@@ -53,6 +97,7 @@ app_settings:
     - wrong_mail_warning
     - close_wrong_mail_warning
     - close_settings
+    - load_main_screen
     - load_settings_mechanics
     - load_settings_technics
     - edit_person
@@ -60,6 +105,7 @@ app_settings:
     - unmark_person
     - edit_person_name
     - edit_person_mail
+    - edit_person_certificate
 */
 use crate::handlers::app_settings::*;
 
@@ -79,6 +125,31 @@ config edit
     - save_feedback_recepient
     - save_feedback_subject
     - save_settings_password
+    - save_watch_directory
+    - save_watch_pattern
+    - save_watch_max_age_hours
+    - save_watch_archive
+    - save_post_send_action
+    - save_archive_directory
+    - save_locale
+    - save_body
+    - save_plate_pattern
+    - save_job_number_pattern
+    - save_merge_file_name
+    - save_attachment_name_template
+    - save_keep_recipients
+    - save_author_in_sender_name
+    - save_delivery_status
+    - save_read_receipt
+    - save_profile_selector
+    - edit_sender_auth
+    - edit_dkim
+    - edit_smime
+    - edit_imap
+    - save_default_profile
+    - add_sender_profile
+    - remove_sender_profile
+    - edit_sender_profile
 */
 use crate::handlers::config_edit::*;
 
@@ -95,19 +166,55 @@ use crate::handlers::feedback::*;
 
 //---------------------------
 
+/* 
+hot_folder
+    - load_new_reports
+    - attach_report
+*/
+use crate::handlers::hot_folder::*;
+
+//---------------------------
+
 /* 
 mail_send
+    - open_send_confirmation
+    - close_send_confirmation
+    - toggle_delivery_status
+    - toggle_read_receipt
     - send
+    - close_send_validation
+    - close_send_error
+    - store_sent_copies
+    - close_sent_copy_warning
     - load_mechanics
     - load_technics
+    - restore_last_recipients
+    - load_author_select
+    - select_author
+    - load_profile_select
+    - select_profile
     - add_person
     - remove_person
     - pick_file
+    - remove_file
+    - load_files
+    - edit_vehicle_info
+    - move_file_up
+    - toggle_merge_pdfs
 */
 use crate::handlers::mail_send::*;
 
 //---------------------------
 
+/* 
+mailbox
+    - load_mailbox_alerts
+    - dismiss_mailbox_alerts
+*/
+use crate::handlers::mailbox::*;
+
+//---------------------------
+
 /* 
 manuals:
     - open_manual
@@ -126,14 +233,39 @@ other_mail
     - add_other_mail_row
     - remove_other_row
     - edit_mail
+    - open_promote_person
+    - close_promote_person
+    - promote_person
 */
 use crate::handlers::other_mail::*;
 
 //---------------------------
 
+/* 
+tutorial
+    - load_tutorial
+    - skip_tutorial
+    - restart_tutorial
+*/
+use crate::handlers::tutorial::*;
+
+//---------------------------
+
+mod cli;
+
+//command line mode for scripts, returns exit code when a command was given
+pub fn run_command_line() -> Option<i32> {
+    cli::run()
+}
+
+//---------------------------
+
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    //held until window is closed, sending from command line is refused meanwhile
+    let _instance_lock = backend::instance_lock::acquire();
+
     tauri::Builder::default()
         .setup(|app| {
             app.manage(AppState {
@@ -142,15 +274,42 @@ pub fn run() {
                 other_mail_list: OtherMailList::default().into(),
                 settings_current_person_id: None.into(),
                 config: Config::load_config().into(),
+                hot_folder: HotFolder::default().into(),
+                send_history: SendHistory::load_history().into(),
+                address_suggestions: AddressSuggestions::load_suggestions().into(),
+                mailbox_alerts: Vec::new().into(),
+                outbox: Outbox::load_outbox().into(),
+                tutorial: Tutorial::load_tutorial().into(),
             });
+            start_mailbox_poller(app.handle().clone());
             Ok(())
         })
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![ //seřadit
             pick_file,
+            remove_file,
+            load_files,
+            edit_vehicle_info,
+            move_file_up,
+            toggle_merge_pdfs,
+            load_new_reports,
+            attach_report,
+            open_send_confirmation,
+            close_send_confirmation,
+            toggle_delivery_status,
+            toggle_read_receipt,
             send,
+            close_send_validation,
+            close_send_error,
+            store_sent_copies,
+            close_sent_copy_warning,
             load_mechanics,
             load_technics,
+            restore_last_recipients,
+            load_author_select,
+            select_author,
+            load_profile_select,
+            select_profile,
             open_other,
             add_other_mail_row,
             close_other,
@@ -158,6 +317,9 @@ pub fn run() {
             remove_person,
             edit_mail,
             remove_other_row,
+            open_promote_person,
+            close_promote_person,
+            promote_person,
             open_manual,
             close_manual,
             open_feedback,
@@ -179,6 +341,7 @@ pub fn run() {
             unmark_person,
             edit_person_name,
             edit_person_mail,
+            edit_person_certificate,
             save_and_close_settings_config,
             save_sender_name,
             save_sender_mail,
@@ -189,10 +352,45 @@ pub fn run() {
             save_feedback_recepient,
             save_feedback_subject,
             save_settings_password,
+            save_watch_directory,
+            save_watch_pattern,
+            save_watch_max_age_hours,
+            save_watch_archive,
+            save_post_send_action,
+            save_archive_directory,
+            save_locale,
+            save_body,
+            save_plate_pattern,
+            save_job_number_pattern,
+            save_merge_file_name,
+            save_attachment_name_template,
+            save_keep_recipients,
+            save_author_in_sender_name,
+            save_delivery_status,
+            save_read_receipt,
+            save_profile_selector,
+            edit_sender_auth,
+            edit_dkim,
+            edit_smime,
+            edit_imap,
+            save_default_profile,
+            add_sender_profile,
+            remove_sender_profile,
+            edit_sender_profile,
             close_wrong_mail_warning,
             close_settings,
+            load_main_screen,
             open_discard_overlay,
             close_discard_overlay,
+            open_settings_addresses,
+            close_settings_addresses,
+            purge_address,
+            toggle_block_address,
+            load_mailbox_alerts,
+            dismiss_mailbox_alerts,
+            load_tutorial,
+            skip_tutorial,
+            restart_tutorial,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
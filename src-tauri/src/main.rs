@@ -1,6 +1,11 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+//release build on windows has no console, cli output is visible only when redirected
 fn main() {
+    if let Some(exit_code) = mailsender_lib::run_command_line() {
+        std::process::exit(exit_code);
+    }
+
     mailsender_lib::run();
 }